@@ -0,0 +1,101 @@
+use critic::{
+    dto::{NewRound, NewTitleCriteria, Tournament, TournamentPairing},
+    prelude::*,
+    swiss,
+};
+
+/// Saves one kind of record.
+///
+/// [`Backend`] lists these as supertraits rather than `where` clauses on
+/// [`Record`], since only supertraits carry over to code that takes a
+/// `T: Backend`.
+pub trait Store<R> {
+    fn save(&mut self, record: &R) -> Result<usize, DbError>;
+}
+
+impl<T: DbConnection, R: Record<T>> Store<R> for T {
+    fn save(&mut self, record: &R) -> Result<usize, DbError> {
+        DbConnection::save(self, record)
+    }
+}
+
+/// Storage the screens run against: every query, and every record they
+/// save. Any backend that supports them all is one.
+pub trait Backend:
+    CriticData
+    + std::fmt::Debug
+    + 'static
+    + Store<NewTitle>
+    + Store<UpdateTitle>
+    + Store<DeleteTitle>
+    + Store<NewTitleCriteria>
+    + Store<DeleteTitleCriteria>
+    + Store<ExcludeTitle>
+    + Store<IncludeTitle>
+    + Store<UpdateTitleDetails>
+    + Store<SetTitleField>
+    + Store<DeleteTitleField>
+    + Store<NewCriteriaGroup>
+    + Store<UpdateCriteriaGroup>
+    + Store<DeleteCriteriaGroup>
+    + Store<GroupAddToTiles>
+    + Store<NewCriterion>
+    + Store<UpdateCriterion>
+    + Store<UpdateCriterionWeight>
+    + Store<DeleteCriterion>
+    + Store<MatchResult>
+    + Store<SkipContest>
+    + Store<UndoLastMatch>
+    + Store<DeleteMatch>
+    + Store<FlipMatch>
+    + Store<Rejudge>
+    + Store<NewTournament>
+    + Store<DeleteTournament>
+    + Store<ScoreTournamentMatch>
+{
+    /// See [`swiss::next_pairing`].
+    fn next_pairing(
+        &mut self,
+        tournament: &Tournament,
+    ) -> Result<Option<TournamentPairing>, DbError>;
+}
+
+impl<T> Backend for T
+where
+    T: CriticData + DbConnection + std::fmt::Debug + 'static,
+    NewTitle: Record<T>,
+    UpdateTitle: Record<T>,
+    DeleteTitle: Record<T>,
+    NewTitleCriteria: Record<T>,
+    DeleteTitleCriteria: Record<T>,
+    ExcludeTitle: Record<T>,
+    IncludeTitle: Record<T>,
+    UpdateTitleDetails: Record<T>,
+    SetTitleField: Record<T>,
+    DeleteTitleField: Record<T>,
+    NewCriteriaGroup: Record<T>,
+    UpdateCriteriaGroup: Record<T>,
+    DeleteCriteriaGroup: Record<T>,
+    GroupAddToTiles: Record<T>,
+    NewCriterion: Record<T>,
+    UpdateCriterion: Record<T>,
+    UpdateCriterionWeight: Record<T>,
+    DeleteCriterion: Record<T>,
+    MatchResult: Record<T>,
+    SkipContest: Record<T>,
+    UndoLastMatch: Record<T>,
+    DeleteMatch: Record<T>,
+    FlipMatch: Record<T>,
+    Rejudge: Record<T>,
+    NewTournament: Record<T>,
+    DeleteTournament: Record<T>,
+    ScoreTournamentMatch: Record<T>,
+    NewRound: Record<T>,
+{
+    fn next_pairing(
+        &mut self,
+        tournament: &Tournament,
+    ) -> Result<Option<TournamentPairing>, DbError> {
+        swiss::next_pairing(self, tournament)
+    }
+}
@@ -0,0 +1,253 @@
+use super::{backend::Backend, popup_area, theme, AppTab};
+use critic::{
+    cycles::{self, Conflict},
+    prelude::*,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Constraint,
+    prelude::Rect,
+    style::Stylize,
+    text::{Line, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+/// A conflict with the names needed to show it.
+#[derive(Debug, Clone)]
+struct ConflictRow {
+    conflict: Conflict,
+    group: i32,
+    criterion: String,
+    cycle: Vec<String>,
+}
+
+impl ConflictRow {
+    fn winner(&self) -> &str {
+        self.cycle.first().map(String::as_str).unwrap_or("?")
+    }
+
+    fn loser(&self) -> &str {
+        self.cycle.get(1).map(String::as_str).unwrap_or("?")
+    }
+}
+
+/// Lists judgments caught in preference cycles and lets them be judged
+/// again.
+#[derive(Debug)]
+pub struct CyclesWidget<T> {
+    db: Rc<RefCell<T>>,
+    rows: Vec<ConflictRow>,
+    rejudge: Option<usize>,
+    state: RefCell<TableState>,
+}
+
+impl<T: Backend> CyclesWidget<T> {
+    pub fn new(db: Rc<RefCell<T>>) -> Self {
+        let rows = conflict_rows(&*db.borrow());
+        let mut state = TableState::default();
+        state.select_first();
+        Self {
+            db,
+            rows,
+            rejudge: None,
+            state: RefCell::new(state),
+        }
+    }
+
+    /// Whether the widget is waiting on a new judgment.
+    pub fn is_rejudging(&self) -> bool {
+        self.rejudge.is_some()
+    }
+
+    fn render_rejudge(&self, row: &ConflictRow, area: Rect, frame: &mut Frame) {
+        let area = popup_area(area, 60, 40);
+        frame.render_widget(Clear, area);
+
+        let matches = row.conflict.preference.matches.len();
+        let text = vec![
+            Line::from(row.criterion.as_str()).centered(),
+            Line::default(),
+            Line::from(vec![
+                row.winner().bold(),
+                "  vs  ".into(),
+                row.loser().bold(),
+            ])
+            .centered(),
+            Line::default(),
+            Line::from(format!(
+                "Replaces {} match{} that preferred {}",
+                matches,
+                if matches == 1 { "" } else { "es" },
+                row.winner()
+            ))
+            .centered(),
+            Line::from(vec![
+                "[←]".blue().bold(),
+                " Left ".into(),
+                "[→]".blue().bold(),
+                " Right ".into(),
+                "[↓]".blue().bold(),
+                " Equal ".into(),
+                "[Esc]".blue().bold(),
+                " Cancel".into(),
+            ])
+            .centered(),
+        ];
+
+        frame.render_widget(
+            Paragraph::new(text).block(Block::default().borders(Borders::ALL).title("Re-judge")),
+            area,
+        );
+    }
+}
+
+impl<T: Backend> AppTab for CyclesWidget<T> {
+    fn render(&self, area: Rect, frame: &mut Frame) {
+        let rows = self.rows.iter().map(|x| {
+            let mut cycle = x.cycle.join(" > ");
+            cycle.push_str(" > ");
+            cycle.push_str(x.winner());
+            Row::new::<Vec<Text>>(vec![
+                x.criterion.as_str().into(),
+                format!(
+                    "{} > {} ({:+.1})",
+                    x.winner(),
+                    x.loser(),
+                    x.conflict.preference.margin
+                )
+                .into(),
+                cycle.into(),
+            ])
+        });
+        let columns = Constraint::from_ratios([(2, 8), (2, 8), (4, 8)]);
+        let summary = if self.rows.is_empty() {
+            "No cycles: every criterion's judgments are consistent".to_string()
+        } else {
+            format!("Judgments to reconsider: {}", self.rows.len())
+        };
+        let table = Table::new(rows, columns)
+            .header(
+                Row::new(vec!["Criterion", "Judgment", "Cycle"])
+                    .style(theme::HIGHLIGHT)
+                    .bottom_margin(1),
+            )
+            .column_spacing(1)
+            .style(theme::DEFAULT)
+            .row_highlight_style(theme::HIGHLIGHT)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Cycles")
+                    .title_bottom(Line::from(summary)),
+            );
+
+        let state = &mut *self.state.borrow_mut();
+        frame.render_stateful_widget(table, area, state);
+
+        if let Some(row) = self.rejudge.and_then(|x| self.rows.get(x)) {
+            self.render_rejudge(row, area, frame);
+        }
+    }
+
+    fn render_footer(&self, area: Rect, frame: &mut Frame) {
+        let help = Paragraph::new(
+            Line::from(vec![
+                " [↑↓/WS]".blue().bold(),
+                " Navigate".into(),
+                " [Enter]".blue().bold(),
+                " Re-judge".into(),
+                " [Esc]".blue().bold(),
+                " Back".into(),
+            ])
+            .left_aligned(),
+        );
+        frame.render_widget(help, area);
+    }
+
+    fn handle_key_events(&mut self, evt: &KeyEvent) -> Result<bool, Box<dyn std::error::Error>> {
+        if let Some(idx) = self.rejudge {
+            let score = match evt.code {
+                KeyCode::Left | KeyCode::Char('a') => Some(1.0),
+                KeyCode::Right | KeyCode::Char('d') => Some(0.0),
+                KeyCode::Down | KeyCode::Char('s') => Some(0.5),
+                _ => None,
+            };
+
+            match (score, self.rows.get(idx)) {
+                (Some(score), Some(row)) => {
+                    let preference = &row.conflict.preference;
+                    let rejudge = Rejudge {
+                        criteria_group: row.group,
+                        criterion: preference.criterion,
+                        a: preference.winner,
+                        b: preference.loser,
+                        score,
+                    };
+                    self.db.borrow_mut().save(&rejudge)?;
+                    self.rows = conflict_rows(&*self.db.borrow());
+                    self.rejudge = None;
+                }
+                _ if evt.code == KeyCode::Esc => self.rejudge = None,
+                _ => {}
+            }
+            return Ok(true);
+        }
+
+        match evt.code {
+            KeyCode::Up | KeyCode::Char('w') => {
+                self.state.borrow_mut().select_previous();
+            }
+            KeyCode::Down | KeyCode::Char('s') => {
+                self.state.borrow_mut().select_next();
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                self.rejudge = self
+                    .state
+                    .borrow()
+                    .selected()
+                    .filter(|x| *x < self.rows.len());
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+fn conflict_rows<T: CriticData>(conn: &T) -> Vec<ConflictRow> {
+    let titles: HashMap<i32, String> = conn
+        .all_titles()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|x| (x.id, x.name))
+        .collect();
+    let mut criteria: HashMap<i32, (i32, String)> = HashMap::new();
+    for group in conn.all_groups().unwrap_or_default() {
+        for criterion in conn.criteria(group.id).unwrap_or_default() {
+            criteria.insert(
+                criterion.id,
+                (group.id, format!("{} - {}", group.name, criterion.name)),
+            );
+        }
+    }
+
+    cycles::conflicts(conn)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|conflict| {
+            let (group, criterion) = criteria.get(&conflict.preference.criterion)?.clone();
+            let cycle = conflict
+                .cycle
+                .iter()
+                .map(|x| titles.get(x).cloned().unwrap_or_else(|| "?".to_string()))
+                .collect();
+            Some(ConflictRow {
+                conflict,
+                group,
+                criterion,
+                cycle,
+            })
+        })
+        .collect()
+}
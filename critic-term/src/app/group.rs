@@ -1,8 +1,9 @@
-use std::{cell::RefCell, i32, rc::Rc};
+use std::{cell::RefCell, rc::Rc};
 
 use critic::{
     dto::{GroupAddToTiles, NewTitleCriteria},
     prelude::*,
+    swiss,
 };
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
@@ -13,7 +14,7 @@ use ratatui::{
 };
 use tui_input::{backend::crossterm::EventHandler, Input};
 
-use super::{modal_input_single_line, popup_area, theme, AppTab};
+use super::{backend::Backend, modal_input_single_line, popup_area, theme, AppTab};
 
 #[derive(Debug, Default, PartialEq, Clone)]
 enum Mode {
@@ -29,6 +30,14 @@ enum Mode {
         group_id: i32,
         id: i32,
     },
+    EditWeight {
+        group_id: i32,
+        id: i32,
+    },
+    NewTournament {
+        group_id: i32,
+        id: i32,
+    },
     NewGroup,
     NewCriteria {
         group_id: i32,
@@ -52,25 +61,17 @@ enum Mode {
     },
 }
 
-fn all_groups(conn: &Connection) -> Vec<CriteriaGroup> {
-    if let Ok(groups) = conn.all_groups() {
-        groups
-    } else {
-        vec![]
-    }
+fn all_groups<T: CriticData>(conn: &T) -> Vec<CriteriaGroup> {
+    conn.all_groups().unwrap_or_default()
 }
 
-fn criteria(conn: &Connection, id: i32) -> Vec<CriteriaGroupItem> {
-    if let Ok(criteria) = conn.criteria(id) {
-        criteria
-    } else {
-        vec![]
-    }
+fn criteria<T: CriticData>(conn: &T, id: i32) -> Vec<CriteriaGroupItem> {
+    conn.criteria(id).unwrap_or_default()
 }
 
 #[derive(Debug, Clone)]
-pub struct GroupWidget {
-    db: Rc<RefCell<Connection>>,
+pub struct GroupWidget<T> {
+    db: Rc<RefCell<T>>,
     groups: Vec<CriteriaGroup>,
     criteria: Vec<CriteriaGroupItem>,
     group_state: RefCell<ListState>,
@@ -79,12 +80,12 @@ pub struct GroupWidget {
     input_state: Input,
 }
 
-impl GroupWidget {
-    pub fn new(db: Rc<RefCell<Connection>>) -> Self {
+impl<T: Backend> GroupWidget<T> {
+    pub fn new(db: Rc<RefCell<T>>) -> Self {
         let (groups, first_group) = {
-            let groups = all_groups(&db.borrow());
-            let first_id: i32 = groups.first().map(|x| x.id).or(Some(i32::MAX)).unwrap();
-            let criteria = criteria(&db.borrow(), first_id);
+            let groups = all_groups(&*db.borrow());
+            let first_id: i32 = groups.first().map(|x| x.id).unwrap_or(i32::MAX);
+            let criteria = criteria(&*db.borrow(), first_id);
             (groups, criteria)
         };
 
@@ -105,7 +106,7 @@ impl GroupWidget {
     }
 }
 
-impl AppTab for GroupWidget {
+impl<T: Backend> AppTab for GroupWidget<T> {
     fn render(&self, area: Rect, frame: &mut ratatui::Frame) {
         let areas = Layout::default()
             .margin(1)
@@ -140,10 +141,18 @@ impl AppTab for GroupWidget {
             .highlight_symbol(">>")
             .repeat_highlight_symbol(true);
 
+        let total_weight: f32 = self.criteria.iter().map(|x| x.weight).sum();
         let criteria_items = self
             .criteria
             .iter()
-            .map(|x| ListItem::new(x.name.as_str()))
+            .map(|x| {
+                let share = if total_weight > 0.0 {
+                    x.weight / total_weight * 100.0
+                } else {
+                    0.0
+                };
+                ListItem::new(format!("{} ({:.0}%)", x.name, share))
+            })
             .collect::<Vec<ListItem>>();
 
         let criteria_block = Block::default()
@@ -180,9 +189,15 @@ impl AppTab for GroupWidget {
             Mode::EditCriteria { group_id: _, id: _ } => {
                 modal_input_single_line("Edit Criterion", area, &self.input_state, frame);
             }
+            Mode::EditWeight { .. } => {
+                modal_input_single_line("Edit Weight", area, &self.input_state, frame);
+            }
+            Mode::NewTournament { .. } => {
+                modal_input_single_line("Swiss Rounds", area, &self.input_state, frame);
+            }
             Mode::DeleteGroup { id: _ } => {
                 let area = popup_area(area, 50, 50);
-                frame.render_widget(Clear::default(), area);
+                frame.render_widget(Clear, area);
 
                 let text = vec![
                     Line::from("Are your sure you want to delete the group?").centered(),
@@ -201,7 +216,7 @@ impl AppTab for GroupWidget {
             }
             Mode::DeleteCriteria { group_id: _, id: _ } => {
                 let area = popup_area(area, 50, 50);
-                frame.render_widget(Clear::default(), area);
+                frame.render_widget(Clear, area);
                 let text = vec![
                     Line::from("Are your sure you want to delete the group?").centered(),
                     Line::from(vec![
@@ -222,7 +237,7 @@ impl AppTab for GroupWidget {
             }
             Mode::PushGroupToAll { id: _ } => {
                 let area = popup_area(area, 50, 50);
-                frame.render_widget(Clear::default(), area);
+                frame.render_widget(Clear, area);
                 let text = vec![
                     Line::from("Are your sure you want to push the group to all titles?")
                         .centered(),
@@ -249,7 +264,7 @@ impl AppTab for GroupWidget {
                 ..
             } => {
                 let area = popup_area(area, 50, 80);
-                frame.render_widget(Clear::default(), area);
+                frame.render_widget(Clear, area);
 
                 let list_items: Vec<ListItem> = titles
                     .iter()
@@ -290,6 +305,10 @@ impl AppTab for GroupWidget {
                 " Edit".into(),
                 Span::styled(" [^e]", theme::HINT),
                 " Edit Titles".into(),
+                Span::styled(" [^w]", theme::HINT),
+                " Weight".into(),
+                Span::styled(" [^t]", theme::HINT),
+                " Tournament".into(),
                 Span::styled(" [^p]", theme::HINT),
                 " Push".into(),
                 Span::styled(" [^d]", theme::HINT),
@@ -318,84 +337,70 @@ impl AppTab for GroupWidget {
                     (KeyCode::Down | KeyCode::Char('s'), _) => {
                         self.group_state.borrow_mut().select_next();
                     }
-                    (KeyCode::Right | KeyCode::Char('d'), _) => {
-                        if !self.groups.is_empty() {
-                            let group_id = {
-                                let idx = self.group_state.borrow().selected().unwrap();
-                                self.groups[idx].id
-                            };
-                            self.mode = Mode::Criteria { group_id };
-                        }
+                    (KeyCode::Right | KeyCode::Char('d'), _) if !self.groups.is_empty() => {
+                        let group_id = {
+                            let idx = self.group_state.borrow().selected().unwrap();
+                            self.groups[idx].id
+                        };
+                        self.mode = Mode::Criteria { group_id };
                     }
                     (KeyCode::Char('a'), KeyModifiers::CONTROL) => {
                         self.input_state = Input::default();
                         self.mode = Mode::NewGroup;
                     }
-                    (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
-                        if !self.groups.is_empty() {
-                            let id = {
-                                let idx = self.group_state.borrow().selected().unwrap();
-                                self.groups[idx].id
-                            };
-                            self.mode = Mode::PushGroupToAll { id };
-                        }
+                    (KeyCode::Char('p'), KeyModifiers::CONTROL) if !self.groups.is_empty() => {
+                        let id = {
+                            let idx = self.group_state.borrow().selected().unwrap();
+                            self.groups[idx].id
+                        };
+                        self.mode = Mode::PushGroupToAll { id };
                     }
-                    (KeyCode::Char('e'), KeyModifiers::CONTROL) => {
-                        if !self.groups.is_empty() {
-                            let id = {
-                                let idx = self.group_state.borrow().selected().unwrap();
-                                self.groups[idx].id
-                            };
-                            let mut db = self.db.borrow_mut();
-                            let conn = &mut *db;
-
-                            let titles = conn
-                                .all_titles()
-                                .expect("Only invalid if the connection is bad");
-
-                            let titles_in_group = conn
-                                .titles_in_group(id)
-                                .expect("Only invalid if the connection is bad");
-
-                            let curr = titles
-                                .iter()
-                                .map(|x| titles_in_group.contains(x))
-                                .collect::<Vec<bool>>();
-
-                            let mut state = ListState::default();
-                            state.select_first();
-
-                            self.mode = Mode::EditGroupsForTitles {
-                                id,
-                                edit: curr.clone(),
-                                curr,
-                                titles,
-                                state: RefCell::new(state),
-                            }
+                    (KeyCode::Char('e'), KeyModifiers::CONTROL) if !self.groups.is_empty() => {
+                        let id = {
+                            let idx = self.group_state.borrow().selected().unwrap();
+                            self.groups[idx].id
+                        };
+                        let mut db = self.db.borrow_mut();
+                        let conn = &mut *db;
+
+                        let titles = conn.all_titles()?;
+                        let titles_in_group = conn.titles_in_group(id)?;
+
+                        let curr = titles
+                            .iter()
+                            .map(|x| titles_in_group.contains(x))
+                            .collect::<Vec<bool>>();
+
+                        let mut state = ListState::default();
+                        state.select_first();
+
+                        self.mode = Mode::EditGroupsForTitles {
+                            id,
+                            edit: curr.clone(),
+                            curr,
+                            titles,
+                            state: RefCell::new(state),
                         }
                     }
-                    (KeyCode::Char('e'), _) => {
-                        if !self.groups.is_empty() {
-                            let (id, value) = {
-                                let idx = self.group_state.borrow().selected().unwrap();
-                                (self.groups[idx].id, self.groups[idx].name.as_str())
-                            };
+                    (KeyCode::Char('e'), _) if !self.groups.is_empty() => {
+                        let (id, value) = {
+                            let idx = self.group_state.borrow().selected().unwrap();
+                            (self.groups[idx].id, self.groups[idx].name.as_str())
+                        };
 
-                            self.mode = Mode::EditGroup { id };
-                            self.input_state = Input::new(value.to_string());
-                        }
+                        self.mode = Mode::EditGroup { id };
+                        self.input_state = Input::new(value.to_string());
                     }
                     _ => {}
                 }
 
                 let post_group_id = self.group_state.borrow().selected();
-                if group_id != post_group_id && post_group_id.is_some() {
+                if let Some(idx) = post_group_id.filter(|_| group_id != post_group_id) {
                     let db = self.db.borrow();
                     let db = &*db;
-                    let idx = post_group_id.unwrap();
                     if idx < self.groups.len() {
                         let group = &self.groups[idx];
-                        self.criteria = criteria(&db, group.id);
+                        self.criteria = criteria(db, group.id);
                         self.criteria_state.borrow_mut().select_first();
                     }
                 }
@@ -417,6 +422,36 @@ impl AppTab for GroupWidget {
                         id,
                     };
                 }
+                (KeyCode::Char('w'), KeyModifiers::CONTROL) if !self.criteria.is_empty() => {
+                    let (id, weight) = {
+                        let idx = self.criteria_state.borrow().selected().unwrap();
+                        (self.criteria[idx].id, self.criteria[idx].weight)
+                    };
+
+                    self.mode = Mode::EditWeight {
+                        group_id: *group_id,
+                        id,
+                    };
+                    self.input_state = Input::new(weight.to_string());
+                }
+                (KeyCode::Char('t'), KeyModifiers::CONTROL) if !self.criteria.is_empty() => {
+                    let id = {
+                        let idx = self.criteria_state.borrow().selected().unwrap();
+                        self.criteria[idx].id
+                    };
+                    let titles = self
+                        .db
+                        .borrow()
+                        .titles_in_group(*group_id)
+                        .unwrap_or_default();
+
+                    self.mode = Mode::NewTournament {
+                        group_id: *group_id,
+                        id,
+                    };
+                    self.input_state =
+                        Input::new(swiss::recommended_rounds(titles.len()).to_string());
+                }
                 (KeyCode::Up | KeyCode::Char('w'), _) => {
                     self.criteria_state.borrow_mut().select_previous();
                 }
@@ -426,19 +461,17 @@ impl AppTab for GroupWidget {
                 (KeyCode::Left | KeyCode::Char('a'), _) => {
                     self.mode = Mode::Group;
                 }
-                (KeyCode::Char('e'), _) => {
-                    if !self.criteria.is_empty() {
-                        let (id, value) = {
-                            let idx = self.criteria_state.borrow().selected().unwrap();
-                            (self.criteria[idx].id, self.criteria[idx].name.as_str())
-                        };
+                (KeyCode::Char('e'), _) if !self.criteria.is_empty() => {
+                    let (id, value) = {
+                        let idx = self.criteria_state.borrow().selected().unwrap();
+                        (self.criteria[idx].id, self.criteria[idx].name.as_str())
+                    };
 
-                        self.mode = Mode::EditCriteria {
-                            group_id: *group_id,
-                            id,
-                        };
-                        self.input_state = Input::new(value.to_string());
-                    }
+                    self.mode = Mode::EditCriteria {
+                        group_id: *group_id,
+                        id,
+                    };
+                    self.input_state = Input::new(value.to_string());
                 }
                 _ => {}
             },
@@ -459,8 +492,8 @@ impl AppTab for GroupWidget {
 
                     conn.save(&request)?;
 
-                    self.groups = all_groups(&conn);
-                    self.criteria = criteria(&conn, *id);
+                    self.groups = all_groups(conn);
+                    self.criteria = criteria(conn, *id);
                     let idx = self.groups.iter().position(|x| &x.id == id);
                     *self.group_state.borrow_mut().selected_mut() = idx;
                     self.mode = Mode::Group;
@@ -488,7 +521,7 @@ impl AppTab for GroupWidget {
 
                     conn.save(&request)?;
 
-                    self.criteria = criteria(&conn, *group_id);
+                    self.criteria = criteria(conn, *group_id);
                     let idx = self.criteria.iter().position(|x| &x.id == id);
                     *self.criteria_state.borrow_mut().selected_mut() = idx;
                     self.mode = Mode::Criteria {
@@ -500,6 +533,68 @@ impl AppTab for GroupWidget {
                     return Ok(true);
                 }
             },
+            Mode::EditWeight { group_id, id } => match (evt.code, evt.modifiers) {
+                (KeyCode::Esc, _) => {
+                    self.mode = Mode::Criteria {
+                        group_id: *group_id,
+                    };
+                }
+                (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
+                    let weight = match self.input_state.value().trim().parse::<f32>() {
+                        Ok(weight) if weight.is_finite() && weight >= 0.0 => weight,
+                        _ => return Ok(true),
+                    };
+
+                    let mut db = self.db.borrow_mut();
+                    let conn = &mut *db;
+
+                    let request = UpdateCriterionWeight { id: *id, weight };
+
+                    conn.save(&request)?;
+
+                    self.criteria = criteria(conn, *group_id);
+                    let idx = self.criteria.iter().position(|x| &x.id == id);
+                    *self.criteria_state.borrow_mut().selected_mut() = idx;
+                    self.mode = Mode::Criteria {
+                        group_id: *group_id,
+                    };
+                }
+                _ => {
+                    self.input_state.handle_event(&Event::Key(*evt));
+                    return Ok(true);
+                }
+            },
+            Mode::NewTournament { group_id, id } => match (evt.code, evt.modifiers) {
+                (KeyCode::Esc, _) => {
+                    self.mode = Mode::Criteria {
+                        group_id: *group_id,
+                    };
+                }
+                (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
+                    let rounds = match self.input_state.value().trim().parse::<i32>() {
+                        Ok(rounds) if rounds > 0 => rounds,
+                        _ => return Ok(true),
+                    };
+
+                    let mut db = self.db.borrow_mut();
+                    let conn = &mut *db;
+
+                    let request = NewTournament {
+                        criterion: *id,
+                        rounds,
+                    };
+
+                    conn.save(&request)?;
+
+                    self.mode = Mode::Criteria {
+                        group_id: *group_id,
+                    };
+                }
+                _ => {
+                    self.input_state.handle_event(&Event::Key(*evt));
+                    return Ok(true);
+                }
+            },
             Mode::NewGroup => match (evt.code, evt.modifiers) {
                 (KeyCode::Esc, _) => {
                     self.mode = Mode::Group;
@@ -514,8 +609,8 @@ impl AppTab for GroupWidget {
                     };
 
                     if let Ok(id) = conn.save(&request) {
-                        self.groups = all_groups(&conn);
-                        self.criteria = criteria(&conn, id as i32);
+                        self.groups = all_groups(conn);
+                        self.criteria = criteria(conn, id as i32);
                         let idx = self.groups.iter().position(|x| x.id == id as i32);
                         *self.group_state.borrow_mut().selected_mut() = idx;
                         self.criteria_state.borrow_mut().select_first();
@@ -543,8 +638,8 @@ impl AppTab for GroupWidget {
                         name: name.to_string(),
                     };
 
-                    if let Ok(_) = conn.save(&request) {
-                        self.criteria = criteria(&conn, *group_id);
+                    if conn.save(&request).is_ok() {
+                        self.criteria = criteria(conn, *group_id);
                         let idx = self.criteria.iter().position(|x| x.name == name);
                         *self.criteria_state.borrow_mut().selected_mut() = idx;
                         self.mode = Mode::Group;
@@ -564,14 +659,14 @@ impl AppTab for GroupWidget {
 
                     let idx = self.group_state.borrow().selected().unwrap();
                     conn.save(&request)?;
-                    self.groups = all_groups(&conn);
+                    self.groups = all_groups(conn);
                     if idx < self.groups.len() {
                         let id = self.groups[idx].id;
-                        self.criteria = criteria(&conn, id);
+                        self.criteria = criteria(conn, id);
                     } else if !self.groups.is_empty() {
                         self.group_state.borrow_mut().select_last();
                         let id = self.groups.last().unwrap().id;
-                        self.criteria = criteria(&conn, id);
+                        self.criteria = criteria(conn, id);
                     } else {
                         self.criteria.clear();
                     }
@@ -591,8 +686,8 @@ impl AppTab for GroupWidget {
                     let request = DeleteCriterion { id: *id };
 
                     let idx = self.criteria_state.borrow().selected().unwrap();
-                    if let Ok(_) = conn.save(&request) {
-                        self.criteria = criteria(&conn, *group_id);
+                    if conn.save(&request).is_ok() {
+                        self.criteria = criteria(conn, *group_id);
                         if idx >= self.criteria.len() {
                             self.criteria_state.borrow_mut().select_last();
                         }
@@ -0,0 +1,370 @@
+use super::{backend::Backend, popup_area, theme, AppTab};
+use critic::{dto, prelude::*};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Constraint,
+    prelude::Rect,
+    style::Stylize,
+    text::{Line, Span, Text},
+    widgets::{
+        Block, Borders, Clear, List, ListItem, ListState, Paragraph, Row, Table, TableState,
+    },
+    Frame,
+};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+const PAGE_SIZE: usize = 30;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+enum Filter {
+    #[default]
+    All,
+    Title(i32, String),
+    Criterion(i32, String),
+}
+
+impl Filter {
+    fn title(&self) -> Option<i32> {
+        match self {
+            Filter::Title(id, _) => Some(*id),
+            _ => None,
+        }
+    }
+
+    fn criterion(&self) -> Option<i32> {
+        match self {
+            Filter::Criterion(id, _) => Some(*id),
+            _ => None,
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Filter::All => "All matches".to_string(),
+            Filter::Title(_, name) => format!("Title: {name}"),
+            Filter::Criterion(_, name) => format!("Criterion: {name}"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+enum Mode {
+    #[default]
+    Browse,
+    Filter {
+        filters: Vec<Filter>,
+        state: RefCell<ListState>,
+    },
+    Delete {
+        id: i32,
+    },
+}
+
+/// Lists every recorded judgment, newest first.
+#[derive(Debug)]
+pub struct HistoryWidget<T> {
+    db: Rc<RefCell<T>>,
+    mode: Mode,
+    filter: Filter,
+    page: usize,
+    rows: Vec<dto::MatchRecord>,
+    titles: HashMap<i32, String>,
+    criteria: HashMap<i32, String>,
+    state: RefCell<TableState>,
+}
+
+impl<T: Backend> HistoryWidget<T> {
+    pub fn new(db: Rc<RefCell<T>>) -> Self {
+        let (titles, criteria) = names(&*db.borrow());
+        let mut widget = Self {
+            db,
+            mode: Mode::default(),
+            filter: Filter::default(),
+            page: 0,
+            rows: vec![],
+            titles,
+            criteria,
+            state: RefCell::new(TableState::default()),
+        };
+        widget.reload();
+        widget
+    }
+
+    fn load(&self, page: usize) -> Vec<dto::MatchRecord> {
+        self.db
+            .borrow()
+            .match_history(
+                self.filter.title(),
+                self.filter.criterion(),
+                PAGE_SIZE,
+                page,
+            )
+            .unwrap_or_default()
+    }
+
+    /// Reloads the current page, falling back a page when it emptied.
+    fn reload(&mut self) {
+        self.rows = self.load(self.page);
+        if self.rows.is_empty() && self.page > 0 {
+            self.page -= 1;
+            self.rows = self.load(self.page);
+        }
+
+        let mut state = self.state.borrow_mut();
+        match state.selected() {
+            Some(idx) if idx < self.rows.len() => {}
+            _ => state.select(if self.rows.is_empty() { None } else { Some(0) }),
+        }
+    }
+
+    fn selected(&self) -> Option<&dto::MatchRecord> {
+        self.state
+            .borrow()
+            .selected()
+            .and_then(|x| self.rows.get(x))
+    }
+
+    fn title(&self, id: i32) -> &str {
+        self.titles.get(&id).map(String::as_str).unwrap_or("?")
+    }
+
+    fn outcome(&self, x: &dto::MatchRecord) -> String {
+        if x.score == 0.5 {
+            return "Equal".to_string();
+        }
+
+        let winner = if x.score > 0.5 { x.a } else { x.b };
+        let strength = (x.score - 0.5).abs() * 2.0;
+        if strength < 1.0 {
+            format!("{} ({:.0}%)", self.title(winner), strength * 100.0)
+        } else {
+            self.title(winner).to_string()
+        }
+    }
+}
+
+impl<T: Backend> AppTab for HistoryWidget<T> {
+    fn render(&self, area: Rect, frame: &mut Frame) {
+        let rows = self.rows.iter().map(|x| {
+            Row::new::<Vec<Text>>(vec![
+                x.time.as_str().into(),
+                self.criteria
+                    .get(&x.criterion)
+                    .map(String::as_str)
+                    .unwrap_or("?")
+                    .into(),
+                self.title(x.a).into(),
+                self.title(x.b).into(),
+                self.outcome(x).into(),
+                format!("{:+.0} / {:+.0}", x.elo_adj_a, x.elo_adj_b).into(),
+                format!("{:+.0} / {:+.0}", x.criterion_adj_a, x.criterion_adj_b).into(),
+            ])
+        });
+        let columns = [
+            Constraint::Length(19),
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Length(11),
+            Constraint::Length(11),
+        ];
+        let table = Table::new(rows, columns)
+            .header(
+                Row::new(vec![
+                    "Time",
+                    "Criterion",
+                    "A",
+                    "B",
+                    "Preferred",
+                    "Group Δ",
+                    "Criterion Δ",
+                ])
+                .style(theme::HIGHLIGHT)
+                .bottom_margin(1),
+            )
+            .column_spacing(1)
+            .style(theme::DEFAULT)
+            .row_highlight_style(theme::HIGHLIGHT)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title_bottom(Line::from(vec![
+                        Span::from(self.filter.label()),
+                        Span::from(format!(" - Page {}", self.page + 1)),
+                    ])),
+            );
+
+        let state = &mut *self.state.borrow_mut();
+        frame.render_stateful_widget(table, area, state);
+
+        match &self.mode {
+            Mode::Browse => {}
+            Mode::Filter { filters, state } => {
+                let area = popup_area(area, 50, 80);
+                frame.render_widget(Clear, area);
+
+                let items: Vec<ListItem> =
+                    filters.iter().map(|x| ListItem::new(x.label())).collect();
+                let list = List::new(items)
+                    .block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .fg(theme::HIGHLIGHT)
+                            .title("Filter"),
+                    )
+                    .fg(theme::DEFAULT)
+                    .highlight_style(theme::HIGHLIGHT);
+
+                let mut tmp_state: ListState = state.borrow().clone();
+                frame.render_stateful_widget(list, area, &mut tmp_state);
+                *state.borrow_mut() = tmp_state;
+            }
+            Mode::Delete { .. } => {
+                let area = popup_area(area, 50, 50);
+                frame.render_widget(Clear, area);
+
+                let text = vec![
+                    Line::from("Delete this judgment and recompute ratings?").centered(),
+                    Line::from(vec!["[Y]es".blue().bold(), "[N]o".blue().bold()]).centered(),
+                ];
+
+                frame.render_widget(
+                    Paragraph::new(text)
+                        .block(Block::default().borders(Borders::ALL).title("Delete Match")),
+                    area,
+                );
+            }
+        }
+    }
+
+    fn render_footer(&self, area: Rect, frame: &mut Frame) {
+        let help = Paragraph::new(
+            Line::from(vec![
+                " [↑↓/WS]".blue().bold(),
+                " Navigate".into(),
+                " [←→/AD]".blue().bold(),
+                " Page".into(),
+                " [f]".blue().bold(),
+                " Filter".into(),
+                " [r]".blue().bold(),
+                " Flip".into(),
+                " [^d]".blue().bold(),
+                " Delete".into(),
+            ])
+            .left_aligned(),
+        );
+        frame.render_widget(help, area);
+    }
+
+    fn handle_key_events(&mut self, evt: &KeyEvent) -> Result<bool, Box<dyn std::error::Error>> {
+        match &mut self.mode {
+            Mode::Browse => match (evt.code, evt.modifiers) {
+                (KeyCode::Up | KeyCode::Char('w'), _) => {
+                    self.state.borrow_mut().select_previous();
+                }
+                (KeyCode::Down | KeyCode::Char('s'), _) => {
+                    self.state.borrow_mut().select_next();
+                }
+                (KeyCode::Char('d'), KeyModifiers::CONTROL) | (KeyCode::Delete, _) => {
+                    if let Some(id) = self.selected().map(|x| x.id) {
+                        self.mode = Mode::Delete { id };
+                    }
+                }
+                (KeyCode::Left | KeyCode::Char('a'), _) if self.page > 0 => {
+                    self.page -= 1;
+                    self.rows = self.load(self.page);
+                    self.state.borrow_mut().select_first();
+                }
+                (KeyCode::Right | KeyCode::Char('d'), _) => {
+                    let rows = self.load(self.page + 1);
+                    if !rows.is_empty() {
+                        self.page += 1;
+                        self.rows = rows;
+                        self.state.borrow_mut().select_first();
+                    }
+                }
+                (KeyCode::Char('f'), _) => {
+                    let mut filters = vec![Filter::All];
+                    let mut titles: Vec<_> = self.titles.iter().collect();
+                    titles.sort_by(|a, b| a.1.cmp(b.1));
+                    filters.extend(
+                        titles
+                            .into_iter()
+                            .map(|(id, name)| Filter::Title(*id, name.clone())),
+                    );
+                    let mut criteria: Vec<_> = self.criteria.iter().collect();
+                    criteria.sort_by(|a, b| a.1.cmp(b.1));
+                    filters.extend(
+                        criteria
+                            .into_iter()
+                            .map(|(id, name)| Filter::Criterion(*id, name.clone())),
+                    );
+
+                    let mut state = ListState::default();
+                    state.select(filters.iter().position(|x| *x == self.filter));
+                    self.mode = Mode::Filter {
+                        filters,
+                        state: RefCell::new(state),
+                    };
+                }
+                (KeyCode::Char('r'), _) => {
+                    if let Some(id) = self.selected().map(|x| x.id) {
+                        self.db.borrow_mut().save(&FlipMatch { id })?;
+                        self.reload();
+                    }
+                }
+                _ => return Ok(false),
+            },
+            Mode::Filter { filters, state } => match evt.code {
+                KeyCode::Up | KeyCode::Char('w') => {
+                    state.borrow_mut().select_previous();
+                }
+                KeyCode::Down | KeyCode::Char('s') => {
+                    state.borrow_mut().select_next();
+                }
+                KeyCode::Enter | KeyCode::Char(' ') => {
+                    if let Some(filter) = state.borrow().selected().and_then(|x| filters.get(x)) {
+                        self.filter = filter.clone();
+                    }
+                    self.mode = Mode::Browse;
+                    self.page = 0;
+                    self.state.borrow_mut().select(None);
+                    self.reload();
+                }
+                KeyCode::Esc => self.mode = Mode::Browse,
+                _ => {}
+            },
+            Mode::Delete { id } => match evt.code {
+                KeyCode::Char('y') => {
+                    let id = *id;
+                    self.db.borrow_mut().save(&DeleteMatch { id })?;
+                    self.mode = Mode::Browse;
+                    self.reload();
+                }
+                KeyCode::Esc | KeyCode::Char('n') => self.mode = Mode::Browse,
+                _ => {}
+            },
+        }
+        Ok(true)
+    }
+}
+
+/// Title names and "Group - Criterion" labels by id.
+fn names<T: CriticData>(conn: &T) -> (HashMap<i32, String>, HashMap<i32, String>) {
+    let titles = conn
+        .all_titles()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|x| (x.id, x.name))
+        .collect();
+
+    let mut criteria = HashMap::new();
+    for group in conn.all_groups().unwrap_or_default() {
+        for criterion in conn.criteria(group.id).unwrap_or_default() {
+            criteria.insert(criterion.id, format!("{} - {}", group.name, criterion.name));
+        }
+    }
+
+    (titles, criteria)
+}
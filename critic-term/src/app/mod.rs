@@ -1,19 +1,25 @@
+mod backend;
+mod cycles;
 mod group;
+mod history_tab;
 mod rate;
 mod title_tab;
 mod top_tab;
+mod versus;
 
 use std::{cell::RefCell, rc::Rc};
 
-use critic::{prelude::Connection, DbConnection};
+use backend::Backend;
+use critic::{DbConnection, DbError};
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use group::GroupWidget;
+use history_tab::HistoryWidget;
 use ratatui::{
     layout::{Constraint, Layout},
     prelude::Rect,
     style::Stylize,
     text::Line,
-    widgets::{Block, Tabs},
+    widgets::{Block, Clear, Paragraph, Tabs, Wrap},
     DefaultTerminal, Frame,
 };
 use rate::RateWidget;
@@ -49,7 +55,7 @@ pub(super) fn modal_input_single_line(
             .centered(),
         );
     let area = edit_popup_area(area, 60, 3);
-    frame.render_widget(Clear::default(), area);
+    frame.render_widget(Clear, area);
     let input = Paragraph::new(state.value()).block(block);
     {
         let width = area.width.max(3) - 3; // Keep 2 for borders and 1 for cursor
@@ -88,25 +94,30 @@ enum ActiveScreen {
     Group,
     Title,
     Top,
+    History,
     Exiting,
 }
 
 impl ActiveScreen {
-    pub fn rate(db: Rc<RefCell<Connection>>) -> (ActiveScreen, Box<dyn AppTab>) {
+    pub fn rate<T: Backend>(db: Rc<RefCell<T>>) -> (ActiveScreen, Box<dyn AppTab>) {
         (ActiveScreen::Rate, Box::new(RateWidget::new(db)))
     }
 
-    pub fn group(db: Rc<RefCell<Connection>>) -> (ActiveScreen, Box<dyn AppTab>) {
+    pub fn group<T: Backend>(db: Rc<RefCell<T>>) -> (ActiveScreen, Box<dyn AppTab>) {
         (ActiveScreen::Group, Box::new(GroupWidget::new(db)))
     }
 
-    pub fn title(db: Rc<RefCell<Connection>>) -> (ActiveScreen, Box<dyn AppTab>) {
+    pub fn title<T: Backend>(db: Rc<RefCell<T>>) -> (ActiveScreen, Box<dyn AppTab>) {
         (ActiveScreen::Title, Box::new(TitleWidget::new(db)))
     }
 
-    pub fn top(db: Rc<RefCell<Connection>>) -> (ActiveScreen, Box<dyn AppTab>) {
+    pub fn top<T: Backend>(db: Rc<RefCell<T>>) -> (ActiveScreen, Box<dyn AppTab>) {
         (ActiveScreen::Top, Box::new(TopWidget::new(db)))
     }
+
+    pub fn history<T: Backend>(db: Rc<RefCell<T>>) -> (ActiveScreen, Box<dyn AppTab>) {
+        (ActiveScreen::History, Box::new(HistoryWidget::new(db)))
+    }
 }
 
 pub(super) trait AppTab: std::fmt::Debug {
@@ -117,23 +128,24 @@ pub(super) trait AppTab: std::fmt::Debug {
 }
 
 #[derive(Debug)]
-pub struct App {
+pub struct App<T> {
     tab: (ActiveScreen, Box<dyn AppTab>),
-    db: Rc<RefCell<Connection>>,
+    db: Rc<RefCell<T>>,
     db_name: String,
+    /// A failed change the user can recover from, shown until dismissed.
+    error: Option<String>,
 }
 
-impl App {
-    pub fn new<T: AsRef<str>>(db: T) -> Self {
+impl<T: Backend + DbConnection> App<T> {
+    pub fn new<P: AsRef<str>>(db: P) -> Result<Self, DbError> {
         let db_name = db.as_ref().to_string();
-        let db = Rc::new(RefCell::new(
-            Connection::open_category(&db_name).expect("A valid db is requred"),
-        ));
-        Self {
+        let db = Rc::new(RefCell::new(T::open_category(&db_name)?));
+        Ok(Self {
             tab: ActiveScreen::rate(db.clone()),
             db,
             db_name,
-        }
+            error: None,
+        })
     }
 
     pub fn run(
@@ -162,29 +174,38 @@ impl App {
     }
 
     fn handle_key_event(&mut self, evt: KeyEvent) -> Result<(), Box<dyn std::error::Error>> {
-        if !(self.tab.1.handle_key_events(&evt)?) {
+        if self.error.take().is_some() {
+            return Ok(());
+        }
+
+        let consumed = match self.tab.1.handle_key_events(&evt) {
+            Ok(consumed) => consumed,
+            Err(err) => match err.downcast_ref::<DbError>() {
+                Some(DbError::NotFound | DbError::Constraint(_)) => {
+                    self.error = Some(err.to_string());
+                    true
+                }
+                _ => return Err(err),
+            },
+        };
+        if !consumed {
             match (evt.code, evt.modifiers) {
                 (KeyCode::Char('c'), KeyModifiers::CONTROL) => self.exit(),
                 (KeyCode::Char('q'), _) => self.exit(),
-                (KeyCode::Char('1'), _) => {
-                    if self.tab.0 != ActiveScreen::Rate {
-                        self.tab = ActiveScreen::rate(self.db.clone());
-                    }
+                (KeyCode::Char('1'), _) if self.tab.0 != ActiveScreen::Rate => {
+                    self.tab = ActiveScreen::rate(self.db.clone());
                 }
-                (KeyCode::Char('2'), _) => {
-                    if self.tab.0 != ActiveScreen::Group {
-                        self.tab = ActiveScreen::group(self.db.clone());
-                    }
+                (KeyCode::Char('2'), _) if self.tab.0 != ActiveScreen::Group => {
+                    self.tab = ActiveScreen::group(self.db.clone());
                 }
-                (KeyCode::Char('3'), _) => {
-                    if self.tab.0 != ActiveScreen::Title {
-                        self.tab = ActiveScreen::title(self.db.clone());
-                    }
+                (KeyCode::Char('3'), _) if self.tab.0 != ActiveScreen::Title => {
+                    self.tab = ActiveScreen::title(self.db.clone());
                 }
-                (KeyCode::Char('4'), _) => {
-                    if self.tab.0 != ActiveScreen::Top {
-                        self.tab = ActiveScreen::top(self.db.clone());
-                    }
+                (KeyCode::Char('4'), _) if self.tab.0 != ActiveScreen::Top => {
+                    self.tab = ActiveScreen::top(self.db.clone());
+                }
+                (KeyCode::Char('5'), _) if self.tab.0 != ActiveScreen::History => {
+                    self.tab = ActiveScreen::history(self.db.clone());
                 }
                 _ => {}
             }
@@ -215,9 +236,15 @@ impl App {
 
         {
             let tabs = Tabs::new(
-                vec!["Rate [1]", "Group [2]", "Title [3]", "Top [4]"]
-                    .iter()
-                    .map(|x| x.fg(theme::DEFAULT)),
+                [
+                    "Rate [1]",
+                    "Group [2]",
+                    "Title [3]",
+                    "Top [4]",
+                    "History [5]",
+                ]
+                .iter()
+                .map(|x| x.fg(theme::DEFAULT)),
             )
             .highlight_style(theme::HIGHLIGHT)
             .select(self.tab.0 as usize)
@@ -232,6 +259,21 @@ impl App {
             self.tab.1.render(inner_area, frame);
         }
 
+        if let Some(error) = &self.error {
+            let area = popup_area(inner_area, 60, 30);
+            frame.render_widget(Clear, area);
+            frame.render_widget(
+                Paragraph::new(vec![
+                    Line::from(error.as_str()).centered(),
+                    Line::default(),
+                    Line::from("[Any key] Dismiss".blue().bold()).centered(),
+                ])
+                .wrap(Wrap { trim: true })
+                .block(Block::bordered().title("Error").red()),
+                area,
+            );
+        }
+
         {
             let region = Layout::horizontal(Constraint::from_ratios([(7, 8), (1, 8)]));
             let [tab, main] = region.areas(footer_area);
@@ -245,13 +287,65 @@ impl App {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use critic::prelude::*;
+    use ratatui::{backend::TestBackend, Terminal};
 
     #[test]
     fn handle_key_event() -> Result<(), Box<dyn std::error::Error>> {
-        let mut app = App::new("Test.db");
+        let mut app = App::<Connection>::new("Test.db")?;
         app.handle_key_event(KeyCode::Char('q').into())?;
         assert_eq!(app.tab.0, ActiveScreen::Exiting);
 
         Ok(())
     }
+
+    #[test]
+    fn screens_run_in_memory() -> Result<(), Box<dyn std::error::Error>> {
+        let mut app = App::<MemoryConnection>::new("memory")?;
+        {
+            let mut db = app.db.borrow_mut();
+            db.save(&NewTitle { name: "A".into() })?;
+            db.save(&NewTitle { name: "B".into() })?;
+            db.save(&NewCriteriaGroup {
+                name: "Group".into(),
+            })?;
+            db.save(&NewCriterion {
+                group: 1,
+                name: "Criterion".into(),
+            })?;
+            db.save(&GroupAddToTiles { id: 1 })?;
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 30))?;
+        for (tab, screen) in [
+            ('1', ActiveScreen::Rate),
+            ('2', ActiveScreen::Group),
+            ('3', ActiveScreen::Title),
+            ('4', ActiveScreen::Top),
+            ('5', ActiveScreen::History),
+        ] {
+            app.handle_key_event(KeyCode::Char(tab).into())?;
+            assert_eq!(app.tab.0, screen);
+            for key in [KeyCode::Down, KeyCode::Left, KeyCode::Esc] {
+                terminal.draw(|frame| app.draw(frame))?;
+                app.handle_key_event(key.into())?;
+            }
+        }
+        assert!(app.error.is_none());
+
+        app.handle_key_event(KeyCode::Char('3').into())?;
+        for line in ["year: 1997", "igdb: 427"] {
+            app.handle_key_event(KeyCode::Char('m').into())?;
+            for c in line.chars() {
+                app.handle_key_event(KeyCode::Char(c).into())?;
+            }
+            app.handle_key_event(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL))?;
+        }
+        terminal.draw(|frame| app.draw(frame))?;
+        let details = app.db.borrow().title_details(1)?;
+        assert_eq!(details.year, Some(1997));
+        assert_eq!(details.fields[0].value, "427");
+
+        Ok(())
+    }
 }
@@ -1,7 +1,12 @@
 use std::{cell::RefCell, rc::Rc};
 
-use critic::{dto::Contest, prelude::*};
-use crossterm::event::{KeyCode, KeyEvent};
+use critic::{
+    dto::{Contest, HeadToHead, ScoreScale, Tournament, TournamentPairing},
+    prelude::*,
+    selection,
+    swiss::{self, RoundProgress},
+};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style, Stylize},
@@ -9,7 +14,9 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 
-use super::{theme, AppTab};
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use super::{backend::Backend, modal_input_single_line, theme, AppTab};
 
 #[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 enum Selected {
@@ -21,16 +28,39 @@ enum Selected {
     Equals,
 }
 
+/// The tournament pairing being judged.
+#[derive(Debug)]
+struct Round {
+    tournament: Tournament,
+    pairing: TournamentPairing,
+    progress: RoundProgress,
+}
+
 #[derive(Debug)]
-pub struct RateWidget {
+pub struct RateWidget<T> {
     contest: Contest,
+    round: Option<Round>,
     selected: Selected,
-    db: Rc<RefCell<Connection>>,
+    /// Steps of preference for the selected side, on `scale`.
+    margin: i32,
+    scale: ScoreScale,
+    /// Contests judged this session, most recent last, so undo can show them again.
+    judged: Vec<Contest>,
+    /// Reason being typed for a skip, if the prompt is open.
+    reason: Option<Input>,
+    /// Whether ratings and the predicted outcome are shown on the cards.
+    /// Hidden by default so they don't sway the judgment.
+    show_ratings: bool,
+    /// Year, platform and creator of each side of `contest`.
+    summaries: [String; 2],
+    /// What the overlay shows, loaded while it is open.
+    ratings: Option<(HeadToHead, EloConfig)>,
+    db: Rc<RefCell<T>>,
 }
 
 #[allow(unused)]
-fn save_match(
-    conn: &mut Connection,
+fn save_match<T: Backend>(
+    conn: &mut T,
     contest: &critic::dto::Contest,
     score: f32,
 ) -> Result<(), critic::DbError> {
@@ -40,30 +70,186 @@ fn save_match(
         a: contest.a.id,
         b: contest.b.id,
         criterion: contest.criterion.id,
-        elo_change: critic::elo::calc_change(contest.a.elo, contest.b.elo, score),
     };
     conn.save(&result).map(|_| ())
 }
 
-impl RateWidget {
-    pub fn new(db: Rc<RefCell<Connection>>) -> Self {
-        let contest = {
-            if let Ok(contest) = db.borrow().next_contest() {
-                contest
-            } else {
-                Contest::default()
+/// Records the match for a tournament pairing along with its score.
+fn score_pairing<T: Backend>(
+    conn: &mut T,
+    contest: &critic::dto::Contest,
+    pairing: &TournamentPairing,
+    score: f32,
+) -> Result<(), critic::DbError> {
+    let result = ScoreTournamentMatch {
+        pairing: pairing.id,
+        criteria_group: contest.criterion.group,
+        criterion: contest.criterion.id,
+        a: contest.a.id,
+        b: contest.b.id,
+        score,
+    };
+    conn.save(&result).map(|_| ())
+}
+
+fn save_skip<T: Backend>(
+    conn: &mut T,
+    contest: &critic::dto::Contest,
+    reason: Option<String>,
+) -> Result<(), critic::DbError> {
+    let skip = SkipContest {
+        criterion: contest.criterion.id,
+        a: contest.a.id,
+        b: contest.b.id,
+        reason,
+    };
+    conn.save(&skip).map(|_| ())
+}
+
+/// The next pair from the active tournament, if there is one, otherwise from
+/// the contest strategy.
+fn next_contest<T: Backend>(conn: &mut T) -> (Contest, Option<Round>) {
+    if let Ok(Some(tournament)) = conn.active_tournament() {
+        if let Ok(Some(pairing)) = conn.next_pairing(&tournament) {
+            // Pairing a new round moves the tournament on.
+            let tournament = conn
+                .active_tournament()
+                .ok()
+                .flatten()
+                .unwrap_or(tournament);
+            let pairings = conn.tournament_pairings(tournament.id).unwrap_or_default();
+            let b = pairing.b.unwrap_or_default();
+
+            if let Ok(Some(contest)) =
+                selection::contest_for(conn, tournament.criterion, pairing.a, b)
+            {
+                let round = Round {
+                    progress: swiss::progress(&tournament, &pairings),
+                    tournament,
+                    pairing,
+                };
+                return (contest, Some(round));
             }
-        };
+        }
+    }
+
+    (selection::next_contest(conn).unwrap_or_default(), None)
+}
 
-        Self {
-            contest,
+impl<T: Backend> RateWidget<T> {
+    pub fn new(db: Rc<RefCell<T>>) -> Self {
+        let scale = db.borrow().score_scale().unwrap_or_default();
+
+        let mut widget = Self {
+            contest: Contest::default(),
+            round: None,
+            margin: scale.max_margin(),
+            scale,
             db,
             selected: Selected::None,
+            judged: Vec::new(),
+            reason: None,
+            show_ratings: false,
+            summaries: Default::default(),
+            ratings: None,
+        };
+        widget.advance();
+        widget
+    }
+}
+
+impl<T: Backend> RateWidget<T> {
+    /// Selects `side`, or steps its margin down on repeated presses,
+    /// wrapping back to the strongest preference.
+    fn select(&mut self, side: Selected) {
+        if self.selected == side && self.margin > 1 {
+            self.margin -= 1;
+        } else {
+            self.selected = side;
+            self.margin = self.scale.max_margin();
+        }
+    }
+
+    fn card_block(&self, side: Selected, style: Style) -> Block<'_> {
+        let block = Block::default().borders(Borders::ALL).style(style);
+        if self.selected == side && self.scale != ScoreScale::Three {
+            block.title_bottom(Line::from(self.scale.label(self.margin)).centered())
+        } else {
+            block
+        }
+    }
+
+    /// Shows `contest`, loading what its cards and the overlay need.
+    fn show(&mut self, contest: Contest, round: Option<Round>) {
+        let summary = |title: i32| {
+            self.db
+                .borrow()
+                .title_details(title)
+                .map(|x| x.summary())
+                .unwrap_or_default()
+        };
+        self.summaries = [summary(contest.a.id), summary(contest.b.id)];
+        self.contest = contest;
+        self.round = round;
+        self.selected = Selected::None;
+        self.load_ratings();
+    }
+
+    /// Moves on to the next contest.
+    fn advance(&mut self) {
+        let (contest, round) = next_contest(&mut *self.db.borrow_mut());
+        self.show(contest, round);
+    }
+
+    fn load_ratings(&mut self) {
+        self.ratings = self.show_ratings.then(|| self.find_ratings()).flatten();
+    }
+
+    /// The criterion ratings of both titles and the expected outcome.
+    fn find_ratings(&self) -> Option<(HeadToHead, EloConfig)> {
+        let db = self.db.borrow();
+        let criterion = &self.contest.criterion;
+        let row = db
+            .head_to_head(self.contest.a.id, self.contest.b.id)
+            .ok()?
+            .into_iter()
+            .find(|x| x.group == criterion.group && x.criterion == Some(criterion.id))?;
+        Some((row, db.elo_config().unwrap_or_default()))
+    }
+}
+
+/// Card text: the title's name and summary, followed by its rating when
+/// shown.
+fn card_text<'a>(
+    name: &'a str,
+    summary: &'a str,
+    style: Style,
+    rating: Option<(&Rating, f32, &EloConfig)>,
+) -> Vec<Line<'a>> {
+    let mut lines = vec![Line::from(Span::styled(name, style))];
+    if !summary.is_empty() {
+        lines.push(Line::from(summary).dim());
+    }
+    if let Some((rating, expected, config)) = rating {
+        let mut matches = format!(
+            "{} match{}",
+            rating.matches,
+            if rating.matches == 1 { "" } else { "es" }
+        );
+        if config.is_provisional(rating.matches) {
+            matches.push_str(" (provisional)");
         }
+        lines.extend([
+            Line::default(),
+            Line::from(format!("Rating {:.0}", rating.elo)).dim(),
+            Line::from(matches).dim(),
+            Line::from(format!("Expected {:.0}%", expected * 100.0)).dim(),
+        ]);
     }
+    lines
 }
 
-impl AppTab for RateWidget {
+impl<T: Backend> AppTab for RateWidget<T> {
     fn render(&self, area: Rect, frame: &mut ratatui::Frame) {
         let title = Line::from(vec![
             self.contest.criterion.group_name.as_str().bold(),
@@ -71,7 +257,19 @@ impl AppTab for RateWidget {
             self.contest.criterion.name.as_str().into(),
         ])
         .alignment(Alignment::Center);
-        frame.render_widget(Block::bordered().title(title), area);
+        let mut block = Block::bordered().title(title);
+        if let Some(Round { progress, .. }) = &self.round {
+            block = block.title_bottom(
+                Line::from(vec![
+                    " Swiss round ".into(),
+                    format!("{}/{}", progress.round, progress.rounds).bold(),
+                    " - ".into(),
+                    format!("{}/{} judged ", progress.judged, progress.pairs).into(),
+                ])
+                .alignment(Alignment::Center),
+            );
+        }
+        frame.render_widget(block, area);
 
         let centered_area = Layout::default()
             .direction(Direction::Vertical)
@@ -149,38 +347,85 @@ impl AppTab for RateWidget {
             alt_area.split(centered_area[3])[1],
         );
 
+        let a_rating = self
+            .ratings
+            .as_ref()
+            .map(|(x, config)| (&x.rating_a, x.expected, config));
+        let b_rating = self
+            .ratings
+            .as_ref()
+            .map(|(x, config)| (&x.rating_b, 1.0 - x.expected, config));
+
         frame.render_widget(
-            Paragraph::new(Line::from(vec![Span::styled(a_str, styles[a_style])]))
-                .wrap(Wrap { trim: false })
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .style(styles[a_style]),
-                )
-                .alignment(Alignment::Center),
+            Paragraph::new(card_text(
+                a_str,
+                &self.summaries[0],
+                styles[a_style],
+                a_rating,
+            ))
+            .wrap(Wrap { trim: false })
+            .block(self.card_block(Selected::Left, styles[a_style]))
+            .alignment(Alignment::Center),
             card_area[1],
         );
 
         frame.render_widget(
-            Paragraph::new(Line::from(vec![Span::styled(b_str, styles[b_style])]))
-                .wrap(Wrap { trim: false })
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .style(styles[b_style]),
-                )
-                .alignment(Alignment::Center),
+            Paragraph::new(card_text(
+                b_str,
+                &self.summaries[1],
+                styles[b_style],
+                b_rating,
+            ))
+            .wrap(Wrap { trim: false })
+            .block(self.card_block(Selected::Right, styles[b_style]))
+            .alignment(Alignment::Center),
             card_area[3],
         );
+
+        if let Some(reason) = &self.reason {
+            modal_input_single_line("Skip Reason", area, reason, frame);
+        }
     }
 
     fn render_footer(&self, area: Rect, frame: &mut ratatui::Frame) {
+        let select = if self.scale == ScoreScale::Three {
+            " Select"
+        } else {
+            " Select (again for margin)"
+        };
+
+        if self.round.is_some() {
+            let help = Paragraph::new(
+                Line::from(vec![
+                    " [←↓→/ASD]".blue().bold(),
+                    select.into(),
+                    " [Enter/Space]".blue().bold(),
+                    " Submit".into(),
+                    " [^x]".blue().bold(),
+                    " End tournament".into(),
+                    " [i]".blue().bold(),
+                    " Ratings".into(),
+                ])
+                .left_aligned(),
+            );
+            frame.render_widget(help, area);
+            return;
+        }
+
         let help = Paragraph::new(
             Line::from(vec![
                 " [↑↓←→/WASD]".blue().bold(),
-                " Select".into(),
+                select.into(),
                 " [Enter/Space]".blue().bold(),
                 " Submit".into(),
+                " [u]".blue().bold(),
+                " Undo".into(),
+                " [r]".blue().bold(),
+                " Skip with reason".into(),
+                " [i]".blue().bold(),
+                " Ratings".into(),
+                " [n/N]".blue().bold(),
+                " Not experienced (criterion/all)".into(),
             ])
             .left_aligned(),
         );
@@ -188,6 +433,47 @@ impl AppTab for RateWidget {
     }
 
     fn handle_key_events(&mut self, evt: &KeyEvent) -> Result<bool, Box<dyn std::error::Error>> {
+        if let Some(reason) = &mut self.reason {
+            match (evt.code, evt.modifiers) {
+                (KeyCode::Esc, _) => {
+                    self.reason = None;
+                }
+                (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
+                    let reason = reason.value().trim().to_string();
+                    save_skip(
+                        &mut *self.db.borrow_mut(),
+                        &self.contest,
+                        Some(reason).filter(|x| !x.is_empty()),
+                    )?;
+
+                    self.advance();
+                    self.reason = None;
+                }
+                _ => {
+                    reason.handle_event(&Event::Key(*evt));
+                }
+            }
+            return Ok(true);
+        }
+
+        if let Some(round) = &self.round {
+            match (evt.code, evt.modifiers) {
+                (KeyCode::Char('x'), KeyModifiers::CONTROL) => {
+                    self.db.borrow_mut().save(&DeleteTournament {
+                        id: round.tournament.id,
+                    })?;
+
+                    self.advance();
+                    return Ok(true);
+                }
+                // Every pairing in a round has to be judged.
+                (KeyCode::Up | KeyCode::Char('w' | 'r' | 'u' | 'n' | 'N'), _) => {
+                    return Ok(true);
+                }
+                _ => {}
+            }
+        }
+
         match evt.code {
             KeyCode::Up | KeyCode::Char('w') => {
                 self.selected = Selected::Skip;
@@ -196,24 +482,70 @@ impl AppTab for RateWidget {
                 self.selected = Selected::Equals;
             }
             KeyCode::Left | KeyCode::Char('a') => {
-                self.selected = Selected::Left;
+                self.select(Selected::Left);
             }
             KeyCode::Right | KeyCode::Char('d') => {
-                self.selected = Selected::Right;
+                self.select(Selected::Right);
             }
-            KeyCode::Enter | KeyCode::Char(' ') => {
-                if self.selected != Selected::None {
+            KeyCode::Enter | KeyCode::Char(' ') if self.selected != Selected::None => {
+                let score = match self.selected {
+                    Selected::Left => Some(self.scale.score(self.margin)),
+                    Selected::Right => Some(self.scale.score(-self.margin)),
+                    Selected::Equals => Some(0.5),
+                    _ => None,
+                };
+                {
                     let mut db = self.db.borrow_mut();
                     let db = &mut *db;
-                    match self.selected {
-                        Selected::Left => save_match(db, &self.contest, 1.0)?,
-                        Selected::Right => save_match(db, &self.contest, 0.0)?,
-                        Selected::Equals => save_match(db, &self.contest, 0.5)?,
-                        _ => {}
+                    match score {
+                        Some(score) => {
+                            match &self.round {
+                                Some(round) => {
+                                    score_pairing(db, &self.contest, &round.pairing, score)?
+                                }
+                                None => save_match(db, &self.contest, score)?,
+                            }
+                            self.judged.push(self.contest.clone());
+                        }
+                        None => save_skip(db, &self.contest, None)?,
                     }
+                }
 
-                    self.contest = db.next_contest()?;
-                    self.selected = Selected::None;
+                self.advance();
+            }
+            KeyCode::Char(c @ ('n' | 'N'))
+                if matches!(self.selected, Selected::Left | Selected::Right) =>
+            {
+                let title = match self.selected {
+                    Selected::Left => self.contest.a.id,
+                    _ => self.contest.b.id,
+                };
+                let criterion = (c == 'n').then_some(self.contest.criterion.id);
+
+                self.db
+                    .borrow_mut()
+                    .save(&ExcludeTitle { title, criterion })?;
+
+                self.advance();
+            }
+            KeyCode::Char('r') => {
+                self.reason = Some(Input::default());
+            }
+            KeyCode::Char('i') => {
+                self.show_ratings = !self.show_ratings;
+                self.load_ratings();
+            }
+            KeyCode::Char('u') => {
+                let undone = self.db.borrow_mut().save(&UndoLastMatch)?;
+                if undone > 0 {
+                    // Undoing a tournament match reopens its pairing, which
+                    // has to be judged as part of the tournament again.
+                    let (next, round) = next_contest(&mut *self.db.borrow_mut());
+                    let contest = match (&round, self.judged.pop()) {
+                        (None, Some(contest)) => contest,
+                        _ => next,
+                    };
+                    self.show(contest, round);
                 }
             }
             _ => {}
@@ -2,23 +2,30 @@ use std::{cell::RefCell, rc::Rc};
 
 use critic::{
     dto::{
-        self, CriteriaGroup, DeleteTitle, DeleteTitleCriteria, NewTitle, NewTitleCriteria, Title,
-        UpdateTitle,
+        self, CriteriaGroup, DeleteTitle, DeleteTitleCriteria, DeleteTitleField, ExcludeTitle,
+        IncludeTitle, NewTitle, NewTitleCriteria, SetTitleField, Title, UpdateTitle,
+        UpdateTitleDetails,
     },
-    prelude::Connection,
-    CriticData, DbConnection,
+    placement::Placement,
+    CriticData,
 };
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::Stylize,
+    symbols,
     text::Line,
-    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    widgets::{
+        Axis, Block, Borders, Chart, Clear, Dataset, GraphType, List, ListItem, ListState,
+        Paragraph, Wrap,
+    },
     Frame,
 };
 use tui_input::{backend::crossterm::EventHandler, Input};
 
-use super::{modal_input_single_line, popup_area, theme, AppTab};
+use super::{
+    backend::Backend, modal_input_single_line, popup_area, theme, versus::VersusWidget, AppTab,
+};
 
 #[derive(Debug, Default, Clone)]
 enum Mode {
@@ -37,6 +44,10 @@ enum Mode {
     DeleteTitle {
         id: i32,
     },
+    EditDetails {
+        id: i32,
+        state: Input,
+    },
     EditGroups {
         title_id: i32,
         id: i32,
@@ -45,26 +56,56 @@ enum Mode {
         all_groups: Vec<dto::CriteriaGroup>,
         state: RefCell<ListState>,
     },
+    ChoosePlacement {
+        title_id: i32,
+        group: dto::CriteriaGroup,
+        criteria: Vec<dto::CriteriaGroupItem>,
+        state: RefCell<ListState>,
+    },
+    Placement {
+        placement: Placement,
+        heading: String,
+        titles: Vec<dto::Title>,
+    },
+    EditExclusions {
+        title_id: i32,
+        cur: Vec<bool>,
+        edit: Vec<bool>,
+        criteria: Vec<dto::CriteriaGroupItem>,
+        state: RefCell<ListState>,
+    },
+    History {
+        name: String,
+        /// Each rating's label alongside its history.
+        series: Vec<(String, dto::RatingHistory)>,
+        selected: usize,
+    },
 }
 
+/// Points a rating may still move by and count as settled.
+const STABLE_WITHIN: f32 = 25.0;
+
 #[derive(Debug, Clone)]
-pub struct TitleWidget {
-    db: Rc<RefCell<Connection>>,
+pub struct TitleWidget<T> {
+    db: Rc<RefCell<T>>,
     mode: Mode,
     titles: Vec<dto::Title>,
     groups: Vec<dto::CriteriaGroup>,
+    exclusions: Vec<dto::Exclusion>,
     titles_state: RefCell<ListState>,
     group_state: RefCell<ListState>,
+    versus: Option<VersusWidget<T>>,
 }
 
-impl TitleWidget {
-    pub fn new(db: Rc<RefCell<Connection>>) -> Self {
+impl<T: Backend> TitleWidget<T> {
+    pub fn new(db: Rc<RefCell<T>>) -> Self {
         let (titles, groups) = {
-            let titles = all_titles(&db.borrow());
-            let first_id: i32 = titles.first().map(|x| x.id).or(Some(i32::MAX)).unwrap();
-            let groups = groups_by_title(&db.borrow(), first_id);
+            let titles = all_titles(&*db.borrow());
+            let first_id: i32 = titles.first().map(|x| x.id).unwrap_or(i32::MAX);
+            let groups = groups_by_title(&*db.borrow(), first_id);
             (titles, groups)
         };
+        let exclusions = db.borrow().exclusions().unwrap_or_default();
 
         let titles_state: RefCell<ListState> = RefCell::default();
         let group_state: RefCell<ListState> = RefCell::default();
@@ -76,14 +117,21 @@ impl TitleWidget {
             mode: Mode::default(),
             titles,
             groups,
+            exclusions,
             titles_state,
             group_state,
+            versus: None,
         }
     }
 }
 
-impl AppTab for TitleWidget {
+impl<T: Backend> AppTab for TitleWidget<T> {
     fn render(&self, area: Rect, frame: &mut Frame) {
+        if let Some(versus) = &self.versus {
+            versus.render(area, frame);
+            return;
+        }
+
         let areas = Layout::default()
             .margin(1)
             .direction(Direction::Horizontal)
@@ -100,7 +148,20 @@ impl AppTab for TitleWidget {
         let titles_items = self
             .titles
             .iter()
-            .map(|x| ListItem::new(x.name.as_str()))
+            .map(|x| {
+                let excluded = self.exclusions.contains(&dto::Exclusion {
+                    title: x.id,
+                    criterion: None,
+                });
+                if excluded {
+                    ListItem::new(Line::from(vec![
+                        x.name.as_str().into(),
+                        " (not experienced)".dim(),
+                    ]))
+                } else {
+                    ListItem::new(x.name.as_str())
+                }
+            })
             .collect::<Vec<ListItem>>();
 
         let titles_block = Block::default()
@@ -137,20 +198,42 @@ impl AppTab for TitleWidget {
         frame.render_stateful_widget(titles, areas[0], &mut titles_state);
         *self.titles_state.borrow_mut() = titles_state;
 
+        let right = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Fill(1), Constraint::Percentage(40)])
+            .split(areas[1]);
+
         let mut group_state: ListState = self.group_state.borrow().clone();
-        frame.render_stateful_widget(groups, areas[1], &mut group_state);
+        frame.render_stateful_widget(groups, right[0], &mut group_state);
         *self.group_state.borrow_mut() = group_state;
 
+        let details = self
+            .titles_state
+            .borrow()
+            .selected()
+            .and_then(|idx| self.titles.get(idx))
+            .and_then(|x| self.db.borrow().title_details(x.id).ok());
+        frame.render_widget(
+            Paragraph::new(details.map(details_text).unwrap_or_default())
+                .wrap(Wrap { trim: false })
+                .block(Block::default().borders(Borders::ALL).title("Details"))
+                .fg(theme::DEFAULT),
+            right[1],
+        );
+
         match &self.mode {
             Mode::NewTitle { state } => {
-                modal_input_single_line("Add Title", area, &state, frame);
+                modal_input_single_line("Add Title", area, state, frame);
             }
             Mode::EditTitle { state, .. } => {
-                modal_input_single_line("Edit Title", area, &state, frame);
+                modal_input_single_line("Edit Title", area, state, frame);
+            }
+            Mode::EditDetails { state, .. } => {
+                modal_input_single_line("Edit Details (key: value)", area, state, frame);
             }
             Mode::DeleteTitle { .. } => {
                 let area = popup_area(area, 50, 50);
-                frame.render_widget(Clear::default(), area);
+                frame.render_widget(Clear, area);
 
                 let text = vec![
                     Line::from("Are your sure you want to delete the Title?").centered(),
@@ -170,7 +253,7 @@ impl AppTab for TitleWidget {
                 ..
             } => {
                 let area = popup_area(area, 50, 80);
-                frame.render_widget(Clear::default(), area);
+                frame.render_widget(Clear, area);
 
                 let list_items: Vec<ListItem> = groups
                     .iter()
@@ -198,22 +281,174 @@ impl AppTab for TitleWidget {
                 frame.render_stateful_widget(list, area, &mut tmp_state);
                 *state.borrow_mut() = tmp_state;
             }
+            Mode::ChoosePlacement {
+                criteria, state, ..
+            } => {
+                let area = popup_area(area, 50, 80);
+                frame.render_widget(Clear, area);
+
+                let list_items: Vec<ListItem> = criteria
+                    .iter()
+                    .map(|x| ListItem::new(x.name.as_str()))
+                    .collect();
+
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .fg(theme::HIGHLIGHT)
+                    .title("Place For Criterion");
+
+                let list = List::new(list_items)
+                    .block(block)
+                    .fg(theme::DEFAULT)
+                    .highlight_style(theme::HIGHLIGHT);
+
+                let mut tmp_state: ListState = state.borrow().clone();
+                frame.render_stateful_widget(list, area, &mut tmp_state);
+                *state.borrow_mut() = tmp_state;
+            }
+            Mode::Placement {
+                placement,
+                heading,
+                titles,
+            } => {
+                let area = popup_area(area, 60, 40);
+                frame.render_widget(Clear, area);
+
+                let name = |id: i32| {
+                    titles
+                        .iter()
+                        .find(|x| x.id == id)
+                        .map(|x| x.name.as_str())
+                        .unwrap_or("?")
+                };
+
+                let text = match (placement.opponent(), placement.position()) {
+                    (Some(opponent), _) => vec![
+                        Line::from(heading.as_str()).centered(),
+                        Line::default(),
+                        Line::from(vec![
+                            name(placement.title).bold(),
+                            "  vs  ".into(),
+                            name(opponent).bold(),
+                        ])
+                        .centered(),
+                        Line::default(),
+                        Line::from(format!(
+                            "At most {} comparisons left",
+                            placement.remaining()
+                        ))
+                        .centered(),
+                        Line::from(vec![
+                            "[←]".blue().bold(),
+                            " Left ".into(),
+                            "[→]".blue().bold(),
+                            " Right ".into(),
+                            "[↓]".blue().bold(),
+                            " Equal ".into(),
+                            "[Esc]".blue().bold(),
+                            " Stop".into(),
+                        ])
+                        .centered(),
+                    ],
+                    (None, position) => vec![
+                        Line::from(heading.as_str()).centered(),
+                        Line::default(),
+                        Line::from(format!(
+                            "{} placed #{} of {}",
+                            name(placement.title),
+                            position.unwrap_or_default() + 1,
+                            placement.ranked.len() + 1
+                        ))
+                        .centered(),
+                        Line::default(),
+                        Line::from(vec!["[Enter]".blue().bold(), " Close".into()]).centered(),
+                    ],
+                };
+
+                frame.render_widget(
+                    Paragraph::new(text)
+                        .block(Block::default().borders(Borders::ALL).title("Placement")),
+                    area,
+                );
+            }
+            Mode::History {
+                name,
+                series,
+                selected,
+            } => render_history(name, series, *selected, area, frame),
+            Mode::EditExclusions {
+                edit,
+                criteria,
+                state,
+                ..
+            } => {
+                let area = popup_area(area, 50, 80);
+                frame.render_widget(Clear, area);
+
+                let list_items: Vec<ListItem> = criteria
+                    .iter()
+                    .zip(edit)
+                    .map(|(x, y)| {
+                        ListItem::new(if *y {
+                            format!("[x] {}", x.name)
+                        } else {
+                            format!("[ ] {}", x.name)
+                        })
+                    })
+                    .collect();
+
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .fg(theme::HIGHLIGHT)
+                    .title("Not Experienced");
+
+                let list = List::new(list_items)
+                    .block(block)
+                    .fg(theme::DEFAULT)
+                    .highlight_style(theme::HIGHLIGHT);
+
+                let mut tmp_state: ListState = state.borrow().clone();
+                frame.render_stateful_widget(list, area, &mut tmp_state);
+                *state.borrow_mut() = tmp_state;
+            }
             _ => {}
         }
     }
 
     fn render_footer(&self, area: Rect, frame: &mut Frame) {
+        if let Some(versus) = &self.versus {
+            versus.render_footer(area, frame);
+            return;
+        }
+
         let help = match &self.mode {
-            Mode::Group { .. } | Mode::EditGroups { .. } => {
-                Paragraph::new(Line::from(vec![" Edit ".into(), "<e>".blue().bold()]))
-                    .right_aligned()
-            }
+            Mode::Group { .. }
+            | Mode::EditGroups { .. }
+            | Mode::EditExclusions { .. }
+            | Mode::ChoosePlacement { .. }
+            | Mode::Placement { .. } => Paragraph::new(Line::from(vec![
+                " Edit ".into(),
+                "<e>".blue().bold(),
+                " Not experienced ".into(),
+                "<x>".blue().bold(),
+                " Place ".into(),
+                "<p>".blue().bold(),
+            ]))
+            .right_aligned(),
             _ => Paragraph::new(
                 Line::from(vec![
                     " [^a] ".blue().bold(),
                     " Add ".into(),
                     "[^d]".blue().bold(),
                     " Delete ".into(),
+                    "[x]".blue().bold(),
+                    " Not experienced ".into(),
+                    "[h]".blue().bold(),
+                    " History ".into(),
+                    "[v]".blue().bold(),
+                    " Versus ".into(),
+                    "[m]".blue().bold(),
+                    " Details ".into(),
                 ])
                 .left_aligned(),
             ),
@@ -222,6 +457,14 @@ impl AppTab for TitleWidget {
     }
 
     fn handle_key_events(&mut self, evt: &KeyEvent) -> Result<bool, Box<dyn std::error::Error>> {
+        if let Some(versus) = &mut self.versus {
+            if evt.code == KeyCode::Esc {
+                self.versus = None;
+                return Ok(true);
+            }
+            return versus.handle_key_events(evt);
+        }
+
         match &mut self.mode {
             Mode::Title => {
                 let title_id = self.titles_state.borrow().selected();
@@ -255,24 +498,63 @@ impl AppTab for TitleWidget {
                     (KeyCode::Down | KeyCode::Char('s'), _) => {
                         self.titles_state.borrow_mut().select_next();
                     }
-                    (KeyCode::Right | KeyCode::Char('d'), _) => {
-                        if !self.titles.is_empty() {
-                            let title_id = self.titles[title_id.unwrap()].id;
-                            self.mode = Mode::Group { title_id };
+                    (KeyCode::Right | KeyCode::Char('d'), _) if !self.titles.is_empty() => {
+                        let title_id = self.titles[title_id.unwrap()].id;
+                        self.mode = Mode::Group { title_id };
+                    }
+                    (KeyCode::Char('h'), _) if !self.titles.is_empty() => {
+                        let title = &self.titles[title_id.unwrap()];
+                        let series = rating_series(&*self.db.borrow(), title.id);
+                        self.mode = Mode::History {
+                            name: title.name.clone(),
+                            series,
+                            selected: 0,
+                        };
+                    }
+                    (KeyCode::Char('m'), _) => {
+                        if let Some(title) = title_id.and_then(|idx| self.titles.get(idx)) {
+                            self.mode = Mode::EditDetails {
+                                id: title.id,
+                                state: Input::default(),
+                            };
+                        }
+                    }
+                    (KeyCode::Char('v'), _) if !self.titles.is_empty() => {
+                        self.versus = Some(VersusWidget::new(self.db.clone(), title_id.unwrap()));
+                    }
+                    (KeyCode::Char('x'), _) if !self.titles.is_empty() => {
+                        let title = self.titles[title_id.unwrap()].id;
+                        let mut db = self.db.borrow_mut();
+                        let conn = &mut *db;
+
+                        let excluded = self.exclusions.contains(&dto::Exclusion {
+                            title,
+                            criterion: None,
+                        });
+                        if excluded {
+                            conn.save(&IncludeTitle {
+                                title,
+                                criterion: None,
+                            })?;
+                        } else {
+                            conn.save(&ExcludeTitle {
+                                title,
+                                criterion: None,
+                            })?;
                         }
+                        self.exclusions = conn.exclusions().unwrap_or_default();
                     }
                     _ => {}
                 }
 
                 let post_title_id = self.titles_state.borrow().selected();
-                if title_id != post_title_id && post_title_id.is_some() {
+                if let Some(idx) = post_title_id.filter(|_| title_id != post_title_id) {
                     let db = self.db.borrow();
                     let db = &*db;
-                    let idx = post_title_id.unwrap();
 
                     if idx < self.titles.len() {
                         let title = &self.titles[idx];
-                        self.groups = groups_by_title(&db, title.id);
+                        self.groups = groups_by_title(db, title.id);
                         self.group_state.borrow_mut().select_first();
                     }
                 }
@@ -293,15 +575,15 @@ impl AppTab for TitleWidget {
                     let db = self.db.borrow();
                     let db = &*db;
 
-                    let all_groups = db.all_groups().expect("SQL failed to connect");
+                    let all_groups = db.all_groups()?;
                     let cur = all_groups
                         .iter()
-                        .map(|x| self.groups.contains(&x))
+                        .map(|x| self.groups.contains(x))
                         .collect::<Vec<bool>>();
                     let edit = cur.clone();
 
                     let mut state = ListState::default();
-                    state.select_first();                    
+                    state.select_first();
 
                     self.mode = Mode::EditGroups {
                         title_id: *title_id,
@@ -312,6 +594,72 @@ impl AppTab for TitleWidget {
                         state: RefCell::new(state),
                     };
                 }
+                KeyCode::Char('p') => {
+                    let Some(group) = self
+                        .group_state
+                        .borrow()
+                        .selected()
+                        .and_then(|idx| self.groups.get(idx))
+                        .cloned()
+                    else {
+                        return Ok(false);
+                    };
+                    let title_id = {
+                        let idx = self.titles_state.borrow().selected().unwrap();
+                        self.titles[idx].id
+                    };
+
+                    let criteria = self.db.borrow().criteria(group.id).unwrap_or_default();
+                    let mut state = ListState::default();
+                    state.select_first();
+
+                    self.mode = Mode::ChoosePlacement {
+                        title_id,
+                        group,
+                        criteria,
+                        state: RefCell::new(state),
+                    };
+                }
+                KeyCode::Char('x') => {
+                    let Some(group) = self
+                        .group_state
+                        .borrow()
+                        .selected()
+                        .and_then(|idx| self.groups.get(idx))
+                    else {
+                        return Ok(false);
+                    };
+                    let title = {
+                        let idx = self.titles_state.borrow().selected().unwrap();
+                        self.titles[idx].id
+                    };
+
+                    let db = self.db.borrow();
+                    let db = &*db;
+
+                    let criteria = db.criteria(group.id).unwrap_or_default();
+                    let cur = criteria
+                        .iter()
+                        .map(|x| {
+                            self.exclusions.contains(&dto::Exclusion {
+                                title,
+                                criterion: Some(x.id),
+                            })
+                        })
+                        .collect::<Vec<bool>>();
+                    let edit = cur.clone();
+
+                    let mut state = ListState::default();
+                    state.select_first();
+
+                    self.mode = Mode::EditExclusions {
+                        title_id: title,
+                        cur,
+                        edit,
+                        criteria,
+                        state: RefCell::new(state),
+                    };
+                }
                 _ => {}
             },
             Mode::EditTitle { id, state } => match (evt.code, evt.modifiers) {
@@ -331,8 +679,8 @@ impl AppTab for TitleWidget {
 
                     conn.save(&request)?;
 
-                    self.titles = all_titles(&conn);
-                    self.groups = groups_by_title(&conn, *id);
+                    self.titles = all_titles(conn);
+                    self.groups = groups_by_title(conn, *id);
                     let idx = self.titles.iter().position(|x| x.id == *id);
                     *self.titles_state.borrow_mut().selected_mut() = idx;
                     self.mode = Mode::Title;
@@ -342,6 +690,19 @@ impl AppTab for TitleWidget {
                     return Ok(true);
                 }
             },
+            Mode::EditDetails { id, state } => match (evt.code, evt.modifiers) {
+                (KeyCode::Esc, _) => {
+                    self.mode = Mode::Title;
+                }
+                (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
+                    edit_details(&mut *self.db.borrow_mut(), *id, state.value())?;
+                    self.mode = Mode::Title;
+                }
+                _ => {
+                    state.handle_event(&Event::Key(*evt));
+                    return Ok(true);
+                }
+            },
             Mode::NewTitle { state } => match (evt.code, evt.modifiers) {
                 (KeyCode::Esc, _) => {
                     self.mode = Mode::Title;
@@ -356,8 +717,8 @@ impl AppTab for TitleWidget {
                     };
 
                     if let Ok(id) = conn.save(&request) {
-                        self.titles = all_titles(&conn);
-                        self.groups = groups_by_title(&conn, id as i32);
+                        self.titles = all_titles(conn);
+                        self.groups = groups_by_title(conn, id as i32);
                         let idx = self.groups.iter().position(|x| x.id == id as i32);
                         *self.group_state.borrow_mut().selected_mut() = idx;
                         self.group_state.borrow_mut().select_first();
@@ -378,14 +739,14 @@ impl AppTab for TitleWidget {
 
                     let idx = self.titles_state.borrow().selected().unwrap();
                     conn.save(&request)?;
-                    self.titles = all_titles(&conn);
+                    self.titles = all_titles(conn);
                     if idx < self.titles.len() {
                         let id = self.titles[idx].id;
-                        self.groups = groups_by_title(&conn, id);
+                        self.groups = groups_by_title(conn, id);
                     } else if !self.titles.is_empty() {
                         self.titles_state.borrow_mut().select_last();
                         let id = self.titles.last().unwrap().id;
-                        self.groups = groups_by_title(&conn, id);
+                        self.groups = groups_by_title(conn, id);
                     } else {
                         self.groups.clear();
                     }
@@ -444,7 +805,7 @@ impl AppTab for TitleWidget {
                         }
                     }
 
-                    let groups = groups_by_title(&conn, *title_id);
+                    let groups = groups_by_title(conn, *title_id);
                     if let Some(idx) = groups.iter().position(|x| x.id == *id) {
                         let g = &groups[idx];
                         *self.group_state.borrow_mut().selected_mut() = Some(idx);
@@ -469,24 +830,334 @@ impl AppTab for TitleWidget {
                 }
                 _ => {}
             },
+            Mode::ChoosePlacement {
+                title_id,
+                group,
+                criteria,
+                state,
+            } => match evt.code {
+                KeyCode::Esc => {
+                    self.mode = Mode::Group {
+                        title_id: *title_id,
+                    };
+                }
+                KeyCode::Enter | KeyCode::Char(' ') => {
+                    let Some(criterion) = state.borrow().selected().and_then(|x| criteria.get(x))
+                    else {
+                        return Ok(true);
+                    };
+
+                    let db = self.db.borrow();
+                    let conn = &*db;
+                    let placement = Placement::start(conn, *title_id, group.id, criterion.id)?;
+
+                    self.mode = Mode::Placement {
+                        placement,
+                        heading: format!("{} - {}", group.name, criterion.name),
+                        titles: all_titles(conn),
+                    };
+                }
+                KeyCode::Up | KeyCode::Char('w') => {
+                    state.borrow_mut().select_previous();
+                }
+                KeyCode::Down | KeyCode::Char('s') => {
+                    state.borrow_mut().select_next();
+                }
+                _ => {}
+            },
+            Mode::History {
+                series, selected, ..
+            } => {
+                match evt.code {
+                    KeyCode::Left | KeyCode::Char('a') => {
+                        *selected = selected
+                            .checked_sub(1)
+                            .unwrap_or(series.len().saturating_sub(1));
+                    }
+                    KeyCode::Right | KeyCode::Char('d') => {
+                        *selected = (*selected + 1) % series.len().max(1);
+                    }
+                    KeyCode::Esc | KeyCode::Enter => self.mode = Mode::Title,
+                    _ => {}
+                }
+                return Ok(true);
+            }
+            Mode::Placement { placement, .. } => {
+                let score = match evt.code {
+                    KeyCode::Left | KeyCode::Char('a') => Some(1.0),
+                    KeyCode::Right | KeyCode::Char('d') => Some(0.0),
+                    KeyCode::Down | KeyCode::Char('s') => Some(0.5),
+                    _ => None,
+                };
+
+                match (score, placement.result(score.unwrap_or_default())) {
+                    (Some(score), Some(result)) => {
+                        self.db.borrow_mut().save(&result)?;
+                        placement.answer(score);
+                    }
+                    _ if matches!(evt.code, KeyCode::Esc | KeyCode::Enter) => {
+                        self.mode = Mode::Group {
+                            title_id: placement.title,
+                        };
+                    }
+                    _ => {}
+                }
+                return Ok(true);
+            }
+            Mode::EditExclusions {
+                title_id,
+                cur,
+                edit,
+                criteria,
+                state,
+            } => match (evt.code, evt.modifiers) {
+                (KeyCode::Esc, _) => {
+                    self.mode = Mode::Group {
+                        title_id: *title_id,
+                    };
+                }
+                (KeyCode::Enter | KeyCode::Char(' '), _) => {
+                    if let Some(idx) = state.borrow_mut().selected() {
+                        edit[idx] = !edit[idx];
+                    }
+                }
+                (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
+                    let mut db = self.db.borrow_mut();
+                    let conn = &mut *db;
+
+                    for (y, c) in cur
+                        .iter()
+                        .zip(edit)
+                        .zip(criteria)
+                        .filter(|((x, y), _)| *x != *y)
+                        .map(|((_, y), c)| (*y, c))
+                    {
+                        let (title, criterion) = (*title_id, Some(c.id));
+                        if y {
+                            conn.save(&ExcludeTitle { title, criterion })?;
+                        } else {
+                            conn.save(&IncludeTitle { title, criterion })?;
+                        }
+                    }
+
+                    self.exclusions = conn.exclusions().unwrap_or_default();
+                    self.mode = Mode::Group {
+                        title_id: *title_id,
+                    };
+                }
+                (KeyCode::Up | KeyCode::Char('w'), _) => {
+                    state.borrow_mut().select_previous();
+                }
+                (KeyCode::Down | KeyCode::Char('s'), _) => {
+                    state.borrow_mut().select_next();
+                }
+                _ => {}
+            },
         }
 
         Ok(false)
     }
 }
 
-fn all_titles(conn: &Connection) -> Vec<Title> {
-    if let Ok(titles) = conn.all_titles() {
-        titles
-    } else {
-        vec![]
+/// `title`'s rating histories labelled by group and criterion.
+fn rating_series<T: CriticData>(conn: &T, title: i32) -> Vec<(String, dto::RatingHistory)> {
+    let groups = conn.all_groups().unwrap_or_default();
+    let label = |x: &dto::RatingHistory| {
+        let group = groups.iter().find(|g| g.id == x.group);
+        let group_name = group.map(|g| g.name.clone()).unwrap_or_default();
+        match x.criterion {
+            None => group_name,
+            Some(id) => {
+                let criterion = group
+                    .and_then(|g| conn.criteria(g.id).ok())
+                    .and_then(|c| c.into_iter().find(|c| c.id == id))
+                    .map(|c| c.name)
+                    .unwrap_or_default();
+                format!("{group_name} - {criterion}")
+            }
+        }
+    };
+
+    conn.rating_history(title)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|x| (label(&x), x))
+        .collect()
+}
+
+fn render_history(
+    name: &str,
+    series: &[(String, dto::RatingHistory)],
+    selected: usize,
+    area: Rect,
+    frame: &mut Frame,
+) {
+    let area = popup_area(area, 80, 80);
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("History: {name}"))
+        .title_bottom(
+            Line::from(vec![
+                "[←→]".blue().bold(),
+                " Rating ".into(),
+                "[Esc]".blue().bold(),
+                " Close".into(),
+            ])
+            .centered(),
+        );
+
+    let Some((label, history)) = series.get(selected) else {
+        frame.render_widget(
+            Paragraph::new(Line::from("No matches played yet").centered()).block(block),
+            area,
+        );
+        return;
+    };
+
+    let points: Vec<(f64, f64)> = std::iter::once(history.start)
+        .chain(history.points.iter().map(|x| x.elo))
+        .enumerate()
+        .map(|(i, elo)| (i as f64, elo as f64))
+        .collect();
+    let (lo, hi) = points
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(lo, hi), (_, y)| {
+            (lo.min(*y), hi.max(*y))
+        });
+    let (lo, hi) = ((lo - 10.0).floor(), (hi + 10.0).ceil());
+    let matches = history.points.len();
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let [info_area, chart_area] =
+        Layout::vertical([Constraint::Length(2), Constraint::Min(0)]).areas(inner);
+
+    let now = history
+        .points
+        .last()
+        .map(|x| x.elo)
+        .unwrap_or(history.start);
+    let stable = match history.stable_since(STABLE_WITHIN) {
+        Some(x) if matches > 1 => format!(
+            "within {STABLE_WITHIN} points since {} (match {} of {matches})",
+            x.time,
+            history
+                .points
+                .iter()
+                .position(|p| p.match_id == x.match_id)
+                .unwrap_or_default()
+                + 1
+        ),
+        _ => "not enough matches to tell".to_string(),
+    };
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::from(vec![
+                label.as_str().bold(),
+                format!(" ({} of {})", selected + 1, series.len()).into(),
+            ]),
+            Line::from(format!("{now:.0} after {matches} matches, {stable}")),
+        ]),
+        info_area,
+    );
+
+    let dataset = Dataset::default()
+        .marker(symbols::Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(theme::HIGHLIGHT)
+        .data(&points);
+    let chart = Chart::new(vec![dataset])
+        .x_axis(
+            Axis::default()
+                .title("Matches")
+                .style(theme::DEFAULT)
+                .bounds([0.0, matches.max(1) as f64])
+                .labels(["0".to_string(), matches.to_string()]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(theme::DEFAULT)
+                .bounds([lo, hi])
+                .labels([format!("{lo:.0}"), format!("{hi:.0}")]),
+        );
+    frame.render_widget(chart, chart_area);
+}
+
+/// The detail pane: typed details, then free-form fields, then notes.
+fn details_text(details: dto::TitleDetails) -> Vec<Line<'static>> {
+    let mut rows = vec![
+        ("Year".to_string(), details.year.map(|x| x.to_string())),
+        ("Platform".to_string(), details.platform),
+        ("Creator".to_string(), details.creator),
+    ];
+    rows.extend(details.fields.into_iter().map(|x| (x.key, Some(x.value))));
+
+    let mut lines: Vec<Line> = rows
+        .into_iter()
+        .map(|(key, value)| {
+            Line::from(vec![
+                format!("{key}: ").dim(),
+                value.unwrap_or_else(|| "-".to_string()).into(),
+            ])
+        })
+        .collect();
+    if let Some(notes) = details.notes {
+        lines.push(Line::default());
+        lines.push(Line::from(notes));
     }
+    lines
 }
 
-fn groups_by_title(conn: &Connection, id: i32) -> Vec<CriteriaGroup> {
-    if let Ok(groups) = conn.groups_by_title(id) {
-        groups
-    } else {
-        vec![]
+/// Applies a `key: value` line from the details editor. `year`,
+/// `platform`, `creator` and `notes` set the typed details and any other
+/// key a free-form field, such as an external ID. An empty value clears it.
+fn edit_details<T: Backend>(
+    conn: &mut T,
+    title: i32,
+    line: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let Some((key, value)) = line
+        .split_once(':')
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .filter(|(key, _)| !key.is_empty())
+    else {
+        return Err("Expected `key: value`".into());
+    };
+    let value = (!value.is_empty()).then(|| value.to_string());
+
+    let details = conn.title_details(title)?;
+    let mut update = UpdateTitleDetails {
+        title,
+        year: details.year,
+        platform: details.platform,
+        creator: details.creator,
+        notes: details.notes,
+    };
+    match key.to_lowercase().as_str() {
+        "year" => update.year = value.map(|x| x.parse()).transpose()?,
+        "platform" => update.platform = value,
+        "creator" => update.creator = value,
+        "notes" => update.notes = value,
+        _ => {
+            let key = key.to_string();
+            match value {
+                Some(value) => conn.save(&SetTitleField { title, key, value })?,
+                None => conn.save(&DeleteTitleField { title, key })?,
+            };
+            return Ok(());
+        }
     }
+    conn.save(&update)?;
+
+    Ok(())
+}
+
+fn all_titles<T: CriticData>(conn: &T) -> Vec<Title> {
+    conn.all_titles().unwrap_or_default()
+}
+
+fn groups_by_title<T: CriticData>(conn: &T, id: i32) -> Vec<CriteriaGroup> {
+    conn.groups_by_title(id).unwrap_or_default()
 }
@@ -1,5 +1,5 @@
-use super::{theme, AppTab};
-use critic::{dto, prelude::*};
+use super::{backend::Backend, cycles::CyclesWidget, theme, AppTab};
+use critic::{bradley_terry, dto, glicko, prelude::*};
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::Constraint,
@@ -11,18 +11,46 @@ use ratatui::{
 };
 use std::{cell::RefCell, rc::Rc};
 
+#[derive(Debug, Default, Clone, PartialEq)]
+enum View {
+    #[default]
+    Group,
+    Composite,
+    Criterion(CriteriaGroupItem),
+}
+
+/// A title's Bradley–Terry strength alongside its live rating.
+#[derive(Debug, Clone)]
+struct FitRow {
+    entry: String,
+    elo: i32,
+    error: i32,
+    live: i32,
+}
+
 #[derive(Debug)]
-pub struct TopWidget {
-    db: Rc<RefCell<Connection>>,
+pub struct TopWidget<T> {
+    db: Rc<RefCell<T>>,
     rows: Vec<dto::TopRow>,
     page: usize,
     criteria: String,
+    view: View,
+    system: RatingSystem,
+    provisional_matches: i32,
+    fit: Option<Vec<FitRow>>,
+    cycles: Option<CyclesWidget<T>>,
     state: RefCell<TableState>,
 }
 
-impl TopWidget {
-    pub fn new(db: Rc<RefCell<Connection>>) -> Self {
-        let rows = top_rows(&*db.borrow(), "", 0);
+impl<T: Backend> TopWidget<T> {
+    pub fn new(db: Rc<RefCell<T>>) -> Self {
+        let rows = top_rows(&*db.borrow(), "", &View::Group, 0);
+        let system = db.borrow().rating_system().unwrap_or_default();
+        let provisional_matches = db
+            .borrow()
+            .elo_config()
+            .unwrap_or_default()
+            .provisional_matches;
         let mut state = TableState::default();
         state.select_first();
         Self {
@@ -30,28 +58,127 @@ impl TopWidget {
             rows,
             page: 0,
             criteria: "".to_string(),
+            view: View::Group,
+            system,
+            provisional_matches,
+            fit: None,
+            cycles: None,
             state: RefCell::new(state),
         }
     }
 }
 
-impl AppTab for TopWidget {
-    fn render(&self, area: Rect, frame: &mut Frame) {
-        let rows = self.rows.iter().map(|x| {
+impl<T: Backend> TopWidget<T> {
+    /// Whether `row`'s rating hasn't settled: too uncertain under Glicko-2,
+    /// or from too few matches under Elo. Bradley–Terry has no such notion.
+    fn is_provisional(&self, row: &dto::TopRow) -> bool {
+        match self.system {
+            RatingSystem::Elo => row.matches < self.provisional_matches,
+            RatingSystem::Glicko2 => row.deviation as f32 > glicko::PROVISIONAL_DEVIATION,
+            RatingSystem::BradleyTerry => false,
+        }
+    }
+
+    fn render_fit(&self, fit: &[FitRow], area: Rect, frame: &mut Frame) {
+        let rows = fit.iter().map(|x| {
             Row::new::<Vec<Text>>(vec![
                 x.entry.as_str().into(),
-                x.group.as_str().into(),
-                x.elo.to_string().into(),
+                format!("{} ± {}", x.elo, 2 * x.error).into(),
+                x.live.to_string().into(),
             ])
         });
-        let columns = Constraint::from_ratios([(3, 8), (3, 8), (2, 8)]);
-        let criteria = Line::from(vec![
-            Span::from("Criteria: "),
+        let columns = Constraint::from_ratios([(4, 8), (2, 8), (2, 8)]);
+        let mut criteria = vec![
+            Span::from("Bradley–Terry: "),
             Span::from(self.criteria.as_str()),
-        ]);
+        ];
+        if let View::Criterion(criterion) = &self.view {
+            criteria.push(Span::from(" - "));
+            criteria.push(Span::from(criterion.name.as_str()));
+        }
+        let table = Table::new(rows, columns)
+            .header(
+                Row::new(vec!["Title", "BT", "Live"])
+                    .style(theme::HIGHLIGHT)
+                    .bottom_margin(1),
+            )
+            .column_spacing(1)
+            .style(theme::DEFAULT)
+            .row_highlight_style(theme::HIGHLIGHT)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title_bottom(Line::from(criteria)),
+            );
+
+        let state = &mut *self.state.borrow_mut();
+        frame.render_stateful_widget(table, area, state);
+    }
+}
+
+impl<T: Backend> AppTab for TopWidget<T> {
+    fn render(&self, area: Rect, frame: &mut Frame) {
+        if let Some(cycles) = &self.cycles {
+            cycles.render(area, frame);
+            return;
+        }
+        if let Some(fit) = &self.fit {
+            self.render_fit(fit, area, frame);
+            return;
+        }
+
+        let rows = self.rows.iter().map(|x| {
+            let rating = match self.system {
+                RatingSystem::Glicko2 => format!("{} ± {}", x.elo, 2 * x.deviation),
+                _ => x.elo.to_string(),
+            };
+            if self.is_provisional(x) {
+                Row::new::<Vec<Text>>(vec![
+                    x.entry.as_str().into(),
+                    x.group.as_str().into(),
+                    Line::from(vec![rating.into(), "?".yellow()]).into(),
+                ])
+                .dim()
+            } else {
+                Row::new::<Vec<Text>>(vec![
+                    x.entry.as_str().into(),
+                    x.group.as_str().into(),
+                    rating.into(),
+                ])
+            }
+        });
+        let columns = Constraint::from_ratios([(3, 8), (3, 8), (2, 8)]);
+        let mut criteria = vec![Span::from("Criteria: "), Span::from(self.criteria.as_str())];
+        match &self.view {
+            View::Group => {}
+            View::Composite => criteria.push(Span::from(" (Composite)")),
+            View::Criterion(criterion) => {
+                criteria.push(Span::from(" - "));
+                criteria.push(Span::from(criterion.name.as_str()));
+            }
+        }
+        if self.rows.iter().any(|x| self.is_provisional(x)) {
+            criteria.push(Span::from("  "));
+            criteria.push("?".yellow());
+            criteria.push(Span::from(match self.system {
+                RatingSystem::Glicko2 => format!(
+                    " provisional: deviation above {}",
+                    glicko::PROVISIONAL_DEVIATION
+                ),
+                _ => format!(
+                    " provisional: fewer than {} matches",
+                    self.provisional_matches
+                ),
+            }));
+        }
+        let criteria = Line::from(criteria);
+        let score = match self.view {
+            View::Composite => "Score",
+            _ => "ELO",
+        };
         let table = Table::new(rows, columns)
             .header(
-                Row::new(vec!["Title", "Criteria", "ELO"])
+                Row::new(vec!["Title", "Criteria", score])
                     .style(theme::HIGHLIGHT)
                     .bottom_margin(1),
             )
@@ -69,6 +196,10 @@ impl AppTab for TopWidget {
     }
 
     fn render_footer(&self, area: Rect, frame: &mut ratatui::Frame) {
+        if let Some(cycles) = &self.cycles {
+            cycles.render_footer(area, frame);
+            return;
+        }
         let help = Paragraph::new(
             Line::from(vec![
                 " [↑↓/WS]".blue().bold(),
@@ -77,6 +208,12 @@ impl AppTab for TopWidget {
                 " Page".into(),
                 " [Enter/Space]".blue().bold(),
                 " Filter".into(),
+                " [Tab]".blue().bold(),
+                " View".into(),
+                " [b]".blue().bold(),
+                " Bradley–Terry".into(),
+                " [c]".blue().bold(),
+                " Cycles".into(),
             ])
             .left_aligned(),
         );
@@ -84,6 +221,16 @@ impl AppTab for TopWidget {
     }
 
     fn handle_key_events(&mut self, evt: &KeyEvent) -> Result<bool, Box<dyn std::error::Error>> {
+        if let Some(cycles) = &mut self.cycles {
+            if evt.code == KeyCode::Esc && !cycles.is_rejudging() {
+                self.cycles = None;
+                let db = self.db.borrow();
+                self.rows = top_rows(&*db, &self.criteria, &self.view, self.page);
+                return Ok(true);
+            }
+            return cycles.handle_key_events(evt);
+        }
+
         match evt.code {
             KeyCode::Up | KeyCode::Char('w') => {
                 self.state.borrow_mut().select_previous();
@@ -95,7 +242,7 @@ impl AppTab for TopWidget {
                 let db = self.db.borrow();
                 let db = &*db;
                 let page = self.page.saturating_sub(1);
-                let rows = top_rows(&db, &self.criteria, page);
+                let rows = top_rows(db, &self.criteria, &self.view, page);
                 if !rows.is_empty() {
                     self.rows = rows;
                     self.page = page;
@@ -106,7 +253,7 @@ impl AppTab for TopWidget {
                 let db = self.db.borrow();
                 let db = &*db;
                 let page = self.page.saturating_add(1);
-                let rows = top_rows(&db, &self.criteria, page);
+                let rows = top_rows(db, &self.criteria, &self.view, page);
                 if !rows.is_empty() {
                     self.rows = rows;
                     self.page = page;
@@ -123,9 +270,34 @@ impl AppTab for TopWidget {
                 } else {
                     self.criteria = "".to_string();
                 }
+                self.view = View::Group;
+                self.fit = None;
                 self.page = 0;
                 self.state.borrow_mut().select_first();
-                self.rows = top_rows(&db, &self.criteria, self.page);
+                self.rows = top_rows(db, &self.criteria, &self.view, self.page);
+            }
+            KeyCode::Tab => {
+                let db = self.db.borrow();
+                let db = &*db;
+                self.view = next_view(db, &self.criteria, &self.view);
+                self.page = 0;
+                self.state.borrow_mut().select_first();
+                self.rows = top_rows(db, &self.criteria, &self.view, self.page);
+                if self.fit.is_some() {
+                    self.fit = Some(fit_rows(db, &self.criteria, &self.view));
+                }
+            }
+            KeyCode::Char('b') if !self.criteria.is_empty() => {
+                let db = self.db.borrow();
+                let db = &*db;
+                self.fit = match self.fit {
+                    Some(_) => None,
+                    None => Some(fit_rows(db, &self.criteria, &self.view)),
+                };
+                self.state.borrow_mut().select_first();
+            }
+            KeyCode::Char('c') => {
+                self.cycles = Some(CyclesWidget::new(self.db.clone()));
             }
             _ => {}
         }
@@ -133,10 +305,74 @@ impl AppTab for TopWidget {
     }
 }
 
-fn top_rows(conn: &Connection, group: &str, page: usize) -> Vec<dto::TopRow> {
-    if let Ok(rows) = conn.top(group, 30, page) {
-        rows
-    } else {
-        vec![]
+fn top_rows<T: CriticData>(conn: &T, group: &str, view: &View, page: usize) -> Vec<dto::TopRow> {
+    match view {
+        View::Group => conn.top(group, 30, page),
+        View::Composite => conn.top_composite(group, 30, page),
+        View::Criterion(criterion) => conn.top_criterion(criterion.id, 30, page),
     }
+    .unwrap_or_default()
+}
+
+/// Fits the whole match history of `group` (or the selected criterion) in one
+/// pass, independent of the order the matches were played.
+fn fit_rows<T: CriticData>(conn: &T, group: &str, view: &View) -> Vec<FitRow> {
+    let Some(group) = find_group(conn, group) else {
+        return vec![];
+    };
+    let criterion = match view {
+        View::Criterion(criterion) => Some(criterion.id),
+        _ => None,
+    };
+
+    let Ok(fit) = bradley_terry::fit_group(conn, group.id, criterion) else {
+        return vec![];
+    };
+    let titles = conn.titles_in_group(group.id).unwrap_or_default();
+    let ratings = conn.ratings().unwrap_or_default();
+    let baseline = conn.elo_config().unwrap_or_default().baseline;
+
+    fit.strengths
+        .iter()
+        .map(|x| FitRow {
+            entry: titles
+                .iter()
+                .find(|t| t.id == x.title)
+                .map(|t| t.name.clone())
+                .unwrap_or_default(),
+            elo: x.elo as i32,
+            error: x.std_error as i32,
+            live: ratings
+                .iter()
+                .find(|r| r.title == x.title && r.group == group.id && r.criterion == criterion)
+                .map(|r| r.rating.elo)
+                .unwrap_or(baseline) as i32,
+        })
+        .collect()
+}
+
+fn find_group<T: CriticData>(conn: &T, name: &str) -> Option<CriteriaGroup> {
+    conn.all_groups()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|x| x.name == name)
+}
+
+/// Cycles Group -> Composite -> each criterion of `group` -> Group. Without a
+/// group filter only the group and composite ratings are available.
+fn next_view<T: CriticData>(conn: &T, group: &str, current: &View) -> View {
+    let criteria = find_group(conn, group)
+        .map(|x| conn.criteria(x.id).unwrap_or_default())
+        .unwrap_or_default();
+
+    let next = match current {
+        View::Group => return View::Composite,
+        View::Composite => criteria.first(),
+        View::Criterion(current) => criteria
+            .iter()
+            .position(|x| x.id == current.id)
+            .and_then(|idx| criteria.get(idx + 1)),
+    };
+
+    next.cloned().map(View::Criterion).unwrap_or_default()
 }
@@ -0,0 +1,211 @@
+use super::{backend::Backend, theme, AppTab};
+use critic::dto;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    prelude::Rect,
+    style::Stylize,
+    text::{Line, Text},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Row, Table},
+    Frame,
+};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+/// Picks two titles and compares them across every group and criterion they
+/// share.
+#[derive(Debug, Clone)]
+pub struct VersusWidget<T> {
+    db: Rc<RefCell<T>>,
+    titles: Vec<dto::Title>,
+    /// Group names by id, and criterion names by id.
+    groups: HashMap<i32, String>,
+    criteria: HashMap<i32, String>,
+    rows: Vec<dto::HeadToHead>,
+    /// Whether the right-hand list has focus.
+    right: bool,
+    a_state: RefCell<ListState>,
+    b_state: RefCell<ListState>,
+}
+
+impl<T: Backend> VersusWidget<T> {
+    /// Starts with `a` on the left and the title after it on the right.
+    pub fn new(db: Rc<RefCell<T>>, a: usize) -> Self {
+        let (titles, groups, criteria) = {
+            let conn = db.borrow();
+            let titles = conn.all_titles().unwrap_or_default();
+            let mut groups = HashMap::new();
+            let mut criteria = HashMap::new();
+            for group in conn.all_groups().unwrap_or_default() {
+                for criterion in conn.criteria(group.id).unwrap_or_default() {
+                    criteria.insert(criterion.id, criterion.name);
+                }
+                groups.insert(group.id, group.name);
+            }
+            (titles, groups, criteria)
+        };
+
+        let b = if a + 1 < titles.len() { a + 1 } else { 0 };
+        let mut widget = Self {
+            db,
+            titles,
+            groups,
+            criteria,
+            rows: vec![],
+            right: true,
+            a_state: RefCell::new(ListState::default().with_selected(Some(a))),
+            b_state: RefCell::new(ListState::default().with_selected(Some(b))),
+        };
+        widget.reload();
+        widget
+    }
+
+    fn selected(&self, state: &RefCell<ListState>) -> Option<&dto::Title> {
+        state.borrow().selected().and_then(|x| self.titles.get(x))
+    }
+
+    fn reload(&mut self) {
+        self.rows = match (self.selected(&self.a_state), self.selected(&self.b_state)) {
+            (Some(a), Some(b)) if a.id != b.id => self
+                .db
+                .borrow()
+                .head_to_head(a.id, b.id)
+                .unwrap_or_default(),
+            _ => vec![],
+        };
+    }
+
+    fn render_list(
+        &self,
+        title: &str,
+        state: &RefCell<ListState>,
+        focused: bool,
+        area: Rect,
+        frame: &mut Frame,
+    ) {
+        let items: Vec<ListItem> = self
+            .titles
+            .iter()
+            .map(|x| ListItem::new(x.name.as_str()))
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .fg(if focused {
+                        theme::HIGHLIGHT
+                    } else {
+                        theme::DEFAULT
+                    })
+                    .title(title.to_string()),
+            )
+            .fg(theme::DEFAULT)
+            .highlight_style(theme::HIGHLIGHT)
+            .highlight_symbol(">>");
+
+        let mut tmp_state: ListState = state.borrow().clone();
+        frame.render_stateful_widget(list, area, &mut tmp_state);
+        *state.borrow_mut() = tmp_state;
+    }
+}
+
+impl<T: Backend> AppTab for VersusWidget<T> {
+    fn render(&self, area: Rect, frame: &mut Frame) {
+        let [lists, table_area] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(40), Constraint::Fill(1)])
+            .areas(area);
+        let [left, right] = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .areas(lists);
+
+        self.render_list("Title A", &self.a_state, !self.right, left, frame);
+        self.render_list("Title B", &self.b_state, self.right, right, frame);
+
+        let name = |state| self.selected(state).map_or("", |x| x.name.as_str());
+        let (a, b) = (name(&self.a_state), name(&self.b_state));
+
+        let rows = self.rows.iter().map(|x| {
+            let label = match x.criterion {
+                None => self.groups.get(&x.group).cloned().unwrap_or_default(),
+                Some(c) => format!("  {}", self.criteria.get(&c).map_or("?", |x| x.as_str())),
+            };
+            let record = match x.score() {
+                Some(score) => format!(
+                    "{}-{}-{} ({:.0}%)",
+                    x.wins(),
+                    x.ties(),
+                    x.losses(),
+                    score * 100.0
+                ),
+                None => "-".to_string(),
+            };
+            let row = Row::new::<Vec<Text>>(vec![
+                label.into(),
+                format!("{:.0}", x.rating_a.elo).into(),
+                record.into(),
+                format!("{:.0}", x.rating_b.elo).into(),
+                format!("{:.0}%", x.expected * 100.0).into(),
+            ]);
+            if x.criterion.is_none() {
+                row.bold()
+            } else {
+                row
+            }
+        });
+        let columns = Constraint::from_ratios([(3, 10), (2, 10), (2, 10), (2, 10), (1, 10)]);
+        let summary = if self.rows.is_empty() {
+            "Pick two titles that share a group"
+        } else {
+            "Record is wins-ties-losses for A; Expected is the chance A is preferred"
+        };
+        let table = Table::new(rows, columns)
+            .header(
+                Row::new(vec!["Criterion", a, "Record", b, "Expected"])
+                    .style(theme::HIGHLIGHT)
+                    .bottom_margin(1),
+            )
+            .column_spacing(1)
+            .style(theme::DEFAULT)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("{a} vs {b}"))
+                    .title_bottom(Line::from(summary)),
+            );
+        frame.render_widget(table, table_area);
+    }
+
+    fn render_footer(&self, area: Rect, frame: &mut Frame) {
+        let help = Paragraph::new(
+            Line::from(vec![
+                " [↑↓/WS]".blue().bold(),
+                " Pick".into(),
+                " [←→/AD]".blue().bold(),
+                " Switch side".into(),
+                " [Esc]".blue().bold(),
+                " Back".into(),
+            ])
+            .left_aligned(),
+        );
+        frame.render_widget(help, area);
+    }
+
+    fn handle_key_events(&mut self, evt: &KeyEvent) -> Result<bool, Box<dyn std::error::Error>> {
+        let state = if self.right {
+            &self.b_state
+        } else {
+            &self.a_state
+        };
+        match evt.code {
+            KeyCode::Up | KeyCode::Char('w') => state.borrow_mut().select_previous(),
+            KeyCode::Down | KeyCode::Char('s') => state.borrow_mut().select_next(),
+            KeyCode::Left | KeyCode::Char('a') => self.right = false,
+            KeyCode::Right | KeyCode::Char('d') => self.right = true,
+            KeyCode::Tab => self.right = !self.right,
+            _ => return Ok(false),
+        }
+        self.reload();
+        Ok(true)
+    }
+}
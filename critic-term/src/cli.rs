@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+
+use clap::Subcommand;
+use critic::{elo, prelude::*, replay};
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Show or change the rating system used by the database
+    RatingSystem {
+        /// One of: elo, glicko2, bradley-terry
+        system: Option<RatingSystem>,
+    },
+    /// Show or change how the Rate tab picks the next pair to compare
+    ContestStrategy {
+        /// One of: closest, information-gain
+        strategy: Option<ContestStrategy>,
+    },
+    /// Show or change the number of points on the Rate tab's scale
+    ScoreScale {
+        /// One of: 3 (win, tie or loss), 5, 7
+        scale: Option<ScoreScale>,
+    },
+    /// Show or change how many hours a skipped pair is kept out of the Rate
+    /// tab
+    SkipCooldown { hours: Option<u32> },
+    /// Show or change the Elo K-factor schedule, rating floor and the
+    /// baseline new titles start from
+    EloConfig {
+        /// Comma separated `rating:k` steps, e.g. 2000:16,1000:32. Ratings
+        /// above a step's rating use its K-factor
+        #[arg(long)]
+        k_schedule: Option<String>,
+        /// K-factor for ratings below every step
+        #[arg(long)]
+        k_default: Option<f32>,
+        /// Matches a title's rating stays provisional for
+        #[arg(long)]
+        provisional_matches: Option<i32>,
+        /// K-factor used while a rating is provisional
+        #[arg(long)]
+        provisional_k: Option<f32>,
+        /// Ratings below the floor stop losing points
+        #[arg(long)]
+        floor: Option<f32>,
+        /// Rating new titles start from; run `recompute` to apply it to
+        /// existing ratings
+        #[arg(long)]
+        baseline: Option<f32>,
+    },
+    /// Reset every rating and replay the match history through the active
+    /// rating system
+    Recompute,
+}
+
+impl Command {
+    pub fn run(self, db: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut conn = Connection::open_category(db)?;
+
+        match self {
+            Command::RatingSystem { system: None } => {
+                println!("{}", conn.rating_system()?);
+            }
+            Command::RatingSystem {
+                system: Some(system),
+            } => {
+                conn.save(&SetRatingSystem { system })?;
+                println!("{system}");
+            }
+            Command::ContestStrategy { strategy: None } => {
+                println!("{}", conn.contest_strategy()?);
+            }
+            Command::ContestStrategy {
+                strategy: Some(strategy),
+            } => {
+                conn.save(&SetContestStrategy { strategy })?;
+                println!("{strategy}");
+            }
+            Command::ScoreScale { scale: None } => {
+                println!("{}", conn.score_scale()?);
+            }
+            Command::ScoreScale { scale: Some(scale) } => {
+                conn.save(&SetScoreScale { scale })?;
+                println!("{scale}");
+            }
+            Command::SkipCooldown { hours: None } => {
+                println!("{}", conn.skip_cooldown()?);
+            }
+            Command::SkipCooldown { hours: Some(hours) } => {
+                conn.save(&SetSkipCooldown { hours })?;
+                println!("{hours}");
+            }
+            Command::EloConfig {
+                k_schedule,
+                k_default,
+                provisional_matches,
+                provisional_k,
+                floor,
+                baseline,
+            } => {
+                let mut config = conn.elo_config()?;
+                let changed = k_schedule.is_some()
+                    || k_default.is_some()
+                    || provisional_matches.is_some()
+                    || provisional_k.is_some()
+                    || floor.is_some()
+                    || baseline.is_some();
+
+                if let Some(schedule) = k_schedule {
+                    config.k_schedule = elo::parse_k_schedule(&schedule)?;
+                }
+                config.k_default = k_default.unwrap_or(config.k_default);
+                config.provisional_matches =
+                    provisional_matches.unwrap_or(config.provisional_matches);
+                config.provisional_k = provisional_k.unwrap_or(config.provisional_k);
+                config.floor = floor.unwrap_or(config.floor);
+                config.baseline = baseline.unwrap_or(config.baseline);
+                if changed {
+                    conn.save(&SetEloConfig {
+                        config: config.clone(),
+                    })?;
+                }
+
+                println!("k-schedule: {}", elo::format_k_schedule(&config.k_schedule));
+                println!("k-default: {}", config.k_default);
+                println!("provisional-matches: {}", config.provisional_matches);
+                println!("provisional-k: {}", config.provisional_k);
+                println!("floor: {}", config.floor);
+                println!("baseline: {}", config.baseline);
+            }
+            Command::Recompute => recompute(&mut conn)?,
+        }
+
+        Ok(())
+    }
+}
+
+fn recompute(conn: &mut Connection) -> Result<(), Box<dyn std::error::Error>> {
+    let report = replay::recompute(conn)?;
+
+    let titles: HashMap<i32, String> = conn
+        .all_titles()?
+        .into_iter()
+        .map(|x| (x.id, x.name))
+        .collect();
+    let mut groups = HashMap::new();
+    let mut criteria = HashMap::new();
+    for group in conn.all_groups()? {
+        for criterion in conn.criteria(group.id)? {
+            criteria.insert(criterion.id, criterion.name);
+        }
+        groups.insert(group.id, group.name);
+    }
+
+    println!(
+        "Replayed {} matches with {}",
+        report.matches,
+        conn.rating_system()?
+    );
+
+    let total: f32 = report.moves.iter().map(|x| x.change().abs()).sum();
+    let largest = report
+        .moves
+        .first()
+        .map(|x| x.change().abs())
+        .unwrap_or(0.0);
+    let average = if report.moves.is_empty() {
+        0.0
+    } else {
+        total / report.moves.len() as f32
+    };
+    println!(
+        "{} titles moved across {} ratings (largest {:.0}, average {:.1})",
+        report.titles_moved(),
+        report.moves.len(),
+        largest,
+        average
+    );
+
+    let unknown = String::from("?");
+    for x in report.moves.iter().take(20) {
+        let title = titles.get(&x.title).unwrap_or(&unknown);
+        let group = groups.get(&x.group).unwrap_or(&unknown);
+        match x.criterion.and_then(|id| criteria.get(&id)) {
+            Some(criterion) => println!(
+                "{:>+6.0}  {} ({} - {})",
+                x.change(),
+                title,
+                group,
+                criterion
+            ),
+            None => println!("{:>+6.0}  {} ({})", x.change(), title, group),
+        }
+    }
+
+    Ok(())
+}
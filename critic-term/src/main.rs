@@ -1,19 +1,29 @@
 mod app;
+mod cli;
 
 use clap::Parser;
+use critic::prelude::Connection;
 
 #[derive(Parser)]
 struct Args {
     /// Category Database
     #[arg(index = 1)]
     category_db: String,
+
+    #[command(subcommand)]
+    command: Option<cli::Command>,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
+    if let Some(command) = args.command {
+        return command.run(&args.category_db);
+    }
+
+    let mut app = app::App::<Connection>::new(args.category_db)?;
     let mut terminal = ratatui::init();
-    let result = app::App::new(args.category_db).run(&mut terminal);
+    let result = app.run(&mut terminal);
     ratatui::restore();
     result
 }
@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+
+use crate::{dto::MatchRecord, CriticData, DbError};
+
+use super::SCALE;
+
+/// Precision of the zero-centred Gaussian prior placed on every parameter.
+/// Keeps titles that never lost (or never won) at a finite strength.
+const PRIOR: f64 = 0.1;
+const MAX_ITERATIONS: usize = 100;
+const TOLERANCE: f64 = 1e-9;
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct Strength {
+    pub title: i32,
+    /// Strength on the Elo scale.
+    pub elo: f32,
+    pub std_error: f32,
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct Fit {
+    /// Strongest title first.
+    pub strengths: Vec<Strength>,
+    /// Davidson's tie parameter, `0.0` when no match ended equal.
+    pub tie: f32,
+}
+
+/// Win, loss and tie weights between two titles, from the first title's
+/// point of view.
+type Outcomes = [f64; 3];
+
+/// Fits a Bradley–Terry model with Davidson's extension for ties to every
+/// match between `titles`, independent of the order the matches were played.
+/// Strengths are centred on `baseline`.
+///
+/// A score of exactly `0.5` counts as a tie; any other score is split into a
+/// partial win and partial loss.
+pub fn fit(baseline: f32, titles: &[i32], matches: &[MatchRecord]) -> Fit {
+    let index: HashMap<i32, usize> = titles.iter().enumerate().map(|(i, x)| (*x, i)).collect();
+
+    let mut pairs: HashMap<(usize, usize), Outcomes> = HashMap::new();
+    for m in matches {
+        let (Some(&i), Some(&j)) = (index.get(&m.a), index.get(&m.b)) else {
+            continue;
+        };
+        if i == j {
+            continue;
+        }
+
+        let s = m.score as f64;
+        let (win, loss, tie) = if (s - 0.5).abs() < f64::EPSILON {
+            (0.0, 0.0, 1.0)
+        } else {
+            (s, 1.0 - s, 0.0)
+        };
+        let (key, win, loss) = if i < j {
+            ((i, j), win, loss)
+        } else {
+            ((j, i), loss, win)
+        };
+
+        let entry = pairs.entry(key).or_default();
+        entry[0] += win;
+        entry[1] += loss;
+        entry[2] += tie;
+    }
+
+    let n = titles.len();
+    let mut x = vec![0.0; n + 1];
+    for _ in 0..MAX_ITERATIONS {
+        let (objective, gradient, hessian) = evaluate(&x, &pairs);
+        let Some(step) = cholesky(&hessian).map(|l| solve(&l, &gradient)) else {
+            break;
+        };
+
+        let mut t = 1.0;
+        let mut next = x.clone();
+        while t > 1e-6 {
+            next = x.iter().zip(&step).map(|(x, s)| x + t * s).collect();
+            if evaluate(&next, &pairs).0 >= objective - TOLERANCE {
+                break;
+            }
+            t /= 2.0;
+        }
+
+        let moved = x
+            .iter()
+            .zip(&next)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0, f64::max);
+        x = next;
+        if moved < TOLERANCE {
+            break;
+        }
+    }
+
+    let (_, _, hessian) = evaluate(&x, &pairs);
+    let variance = cholesky(&hessian)
+        .map(|l| {
+            (0..n)
+                .map(|i| {
+                    let mut e = vec![0.0; n + 1];
+                    e[i] = 1.0;
+                    solve(&l, &e)[i]
+                })
+                .collect()
+        })
+        .unwrap_or_else(|| vec![f64::INFINITY; n]);
+
+    let mut strengths: Vec<Strength> = titles
+        .iter()
+        .zip(&x)
+        .zip(variance)
+        .map(|((title, theta), variance)| Strength {
+            title: *title,
+            elo: baseline + *theta as f32 * SCALE,
+            std_error: variance.sqrt() as f32 * SCALE,
+        })
+        .collect();
+    strengths.sort_by(|a, b| b.elo.total_cmp(&a.elo));
+
+    let any_ties = pairs.values().any(|x| x[2] > 0.0);
+    Fit {
+        strengths,
+        tie: if any_ties { x[n].exp() as f32 } else { 0.0 },
+    }
+}
+
+/// Fits every match recorded for `group`, or only those for `criterion`.
+pub fn fit_group<T: CriticData>(
+    connection: &T,
+    group: i32,
+    criterion: Option<i32>,
+) -> Result<Fit, DbError> {
+    let titles: Vec<i32> = connection
+        .titles_in_group(group)?
+        .into_iter()
+        .map(|x| x.id)
+        .collect();
+    let matches: Vec<MatchRecord> = connection
+        .matches()?
+        .into_iter()
+        .filter(|x| x.criteria_group == group && criterion.is_none_or(|c| c == x.criterion))
+        .collect();
+
+    Ok(fit(connection.elo_config()?.baseline, &titles, &matches))
+}
+
+/// Returns the log-posterior, its gradient and the negated Hessian for the
+/// parameters `x`: one log-strength per title followed by the log of the tie
+/// parameter.
+fn evaluate(
+    x: &[f64],
+    pairs: &HashMap<(usize, usize), Outcomes>,
+) -> (f64, Vec<f64>, Vec<Vec<f64>>) {
+    let dim = x.len();
+    let tie = dim - 1;
+
+    let mut objective = -0.5 * PRIOR * x.iter().map(|x| x * x).sum::<f64>();
+    let mut gradient: Vec<f64> = x.iter().map(|x| -PRIOR * x).collect();
+    let mut hessian = vec![vec![0.0; dim]; dim];
+    for (i, row) in hessian.iter_mut().enumerate() {
+        row[i] = PRIOR;
+    }
+
+    // Win for i, win for j and tie, as coefficients of (theta_i, theta_j, delta).
+    const A: [[f64; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.5, 0.5, 1.0]];
+
+    for (&(i, j), outcomes) in pairs {
+        let count: f64 = outcomes.iter().sum();
+        let params = [x[i], x[j], x[tie]];
+
+        let z: Vec<f64> = A
+            .iter()
+            .map(|a| a.iter().zip(&params).map(|(a, p)| a * p).sum())
+            .collect();
+        let max = z.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let log_d = max + z.iter().map(|z| (z - max).exp()).sum::<f64>().ln();
+        let p: Vec<f64> = z.iter().map(|z| (z - log_d).exp()).collect();
+
+        objective += outcomes.iter().zip(&z).map(|(o, z)| o * z).sum::<f64>() - count * log_d;
+
+        let mut mean = [0.0; 3];
+        for (p, a) in p.iter().zip(&A) {
+            for k in 0..3 {
+                mean[k] += p * a[k];
+            }
+        }
+
+        let idx = [i, j, tie];
+        for k in 0..3 {
+            let observed: f64 = outcomes.iter().zip(&A).map(|(o, a)| o * a[k]).sum();
+            gradient[idx[k]] += observed - count * mean[k];
+
+            for l in 0..3 {
+                let second: f64 = p.iter().zip(&A).map(|(p, a)| p * a[k] * a[l]).sum();
+                hessian[idx[k]][idx[l]] += count * (second - mean[k] * mean[l]);
+            }
+        }
+    }
+
+    (objective, gradient, hessian)
+}
+
+/// Lower triangular factor of a symmetric positive definite matrix.
+fn cholesky(a: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = a.len();
+    let mut l = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let sum: f64 = (0..j).map(|k| l[i][k] * l[j][k]).sum();
+            if i == j {
+                let d = a[i][i] - sum;
+                if d <= 0.0 {
+                    return None;
+                }
+                l[i][j] = d.sqrt();
+            } else {
+                l[i][j] = (a[i][j] - sum) / l[j][j];
+            }
+        }
+    }
+    Some(l)
+}
+
+/// Solves `L Lᵀ x = b` given the Cholesky factor `L`.
+fn solve(l: &[Vec<f64>], b: &[f64]) -> Vec<f64> {
+    let n = b.len();
+    let mut y = vec![0.0; n];
+    for i in 0..n {
+        let sum: f64 = (0..i).map(|k| l[i][k] * y[k]).sum();
+        y[i] = (b[i] - sum) / l[i][i];
+    }
+
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let sum: f64 = (i + 1..n).map(|k| l[k][i] * x[k]).sum();
+        x[i] = (y[i] - sum) / l[i][i];
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(a: i32, b: i32, score: f32) -> MatchRecord {
+        MatchRecord {
+            a,
+            b,
+            score,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn orders_titles_by_strength() {
+        let matches: Vec<MatchRecord> = [(1, 2, 1.0), (2, 3, 1.0), (1, 3, 1.0), (3, 1, 0.5)]
+            .iter()
+            .cycle()
+            .take(12)
+            .map(|(a, b, s)| record(*a, *b, *s))
+            .collect();
+
+        let fit = fit(1000.0, &[1, 2, 3], &matches);
+
+        let order: Vec<i32> = fit.strengths.iter().map(|x| x.title).collect();
+        assert_eq!(order, vec![1, 2, 3]);
+        assert!(fit.tie > 0.0);
+        assert!(fit.strengths.iter().all(|x| x.std_error > 0.0));
+    }
+
+    #[test]
+    fn independent_of_match_order() {
+        let mut matches = vec![
+            record(1, 2, 1.0),
+            record(2, 1, 0.5),
+            record(2, 3, 0.0),
+            record(1, 3, 1.0),
+        ];
+        let forward = fit(1000.0, &[1, 2, 3], &matches);
+        matches.reverse();
+        let backward = fit(1000.0, &[1, 2, 3], &matches);
+
+        for (a, b) in forward.strengths.iter().zip(&backward.strengths) {
+            assert_eq!(a.title, b.title);
+            assert!((a.elo - b.elo).abs() < 0.01);
+        }
+    }
+}
@@ -0,0 +1,35 @@
+mod fit;
+
+pub use fit::*;
+
+use crate::{dto::Rating, rating::RatingModel};
+
+/// Rating points per unit of Bradley–Terry log-strength, matching the Elo
+/// scale so the two can be compared directly.
+const SCALE: f32 = 400.0 / std::f32::consts::LN_10;
+const LEARNING_RATE: f32 = 32.0;
+
+/// Online Bradley–Terry model fitted by stochastic gradient ascent with a
+/// fixed learning rate.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BradleyTerry;
+
+impl RatingModel for BradleyTerry {
+    fn expected(&self, a: &Rating, b: &Rating) -> f32 {
+        1.0 / (1.0 + ((b.elo - a.elo) / SCALE).exp())
+    }
+
+    fn calc_change(&self, a: Rating, b: Rating, s: f32) -> (Rating, Rating) {
+        let c = LEARNING_RATE * (s - self.expected(&a, &b));
+        (
+            Rating {
+                elo: a.elo + c,
+                ..a
+            },
+            Rating {
+                elo: b.elo - c,
+                ..b
+            },
+        )
+    }
+}
@@ -0,0 +1,15 @@
+use crate::{DbConnection, DbError, Record};
+
+use super::MemoryConnection;
+
+impl DbConnection for MemoryConnection {
+    /// Starts empty whatever the path, since nothing is read from or written
+    /// to disk.
+    fn open_category<T: AsRef<std::path::Path>>(_path: T) -> Result<Self, DbError> {
+        Ok(Self::new())
+    }
+
+    fn save<T: Record<Self>>(&mut self, record: &T) -> Result<usize, DbError> {
+        record.save(self)
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{
+    dto::{DeleteCriterion, NewCriterion, UpdateCriterion, UpdateCriterionWeight},
+    DbError, Record,
+};
+
+use super::{foreign_key, insert, unique, CriterionRow, MemoryConnection};
+
+impl MemoryConnection {
+    fn criterion_named(&self, group: i32, name: &str) -> Option<i32> {
+        self.criteria
+            .iter()
+            .find(|(_, x)| x.group == group && x.name == name)
+            .map(|(id, _)| *id)
+    }
+}
+
+impl Record<MemoryConnection> for UpdateCriterion {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        let Some(group) = connection.criteria.get(&self.id).map(|x| x.group) else {
+            return Ok(0);
+        };
+        if connection
+            .criterion_named(group, &self.name)
+            .is_some_and(|x| x != self.id)
+        {
+            return Err(unique("criteria.value, criteria.group_id"));
+        }
+
+        if let Some(row) = connection.criteria.get_mut(&self.id) {
+            row.name.clone_from(&self.name);
+        }
+        Ok(1)
+    }
+}
+
+impl Record<MemoryConnection> for UpdateCriterionWeight {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        match connection.criteria.get_mut(&self.id) {
+            Some(row) => {
+                row.weight = self.weight;
+                Ok(1)
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+impl Record<MemoryConnection> for NewCriterion {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        foreign_key(connection.criteria_group.contains_key(&self.group))?;
+        if connection.criterion_named(self.group, &self.name).is_some() {
+            return Ok(0);
+        }
+
+        let row = CriterionRow {
+            group: self.group,
+            name: self.name.clone(),
+            weight: 1.0,
+        };
+        insert(&mut connection.criteria, &mut connection.last_insert, row);
+        Ok(1)
+    }
+}
+
+impl Record<MemoryConnection> for DeleteCriterion {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        Ok(connection.delete_criterion(self.id))
+    }
+}
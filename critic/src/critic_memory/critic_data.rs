@@ -0,0 +1,496 @@
+use std::{
+    cmp::Reverse,
+    hash::{BuildHasher, RandomState},
+};
+
+use crate::{dto, elo, head_to_head, replay, CriticData, DbError};
+
+use super::{now, timestamp, MemoryConnection, RatingRow};
+
+/// One page of `rows`, `count` long.
+fn page<T>(rows: Vec<T>, count: usize, page: usize) -> Vec<T> {
+    rows.into_iter().skip(page * count).take(count).collect()
+}
+
+fn top_row(group: &str, entry: &str, elo: f32, deviation: f32, matches: i32) -> dto::TopRow {
+    dto::TopRow {
+        group: group.to_string(),
+        entry: entry.to_string(),
+        elo: elo as i32,
+        deviation: deviation as i32,
+        matches,
+    }
+}
+
+impl MemoryConnection {
+    fn title_name(&self, id: i32) -> &str {
+        self.titles.get(&id).map_or("", String::as_str)
+    }
+
+    fn group_name(&self, id: i32) -> &str {
+        self.criteria_group.get(&id).map_or("", String::as_str)
+    }
+
+    /// Start of the skip cool-down, in seconds since the Unix epoch.
+    fn cooldown_start(&self) -> Result<u64, DbError> {
+        Ok(now().saturating_sub(u64::from(self.skip_cooldown()?) * 3_600))
+    }
+
+    fn match_record(&self, id: i32) -> Option<dto::MatchRecord> {
+        let x = self.match_history.get(&id)?;
+        Some(dto::MatchRecord {
+            id,
+            criteria_group: self.criteria.get(&x.criterion)?.group,
+            criterion: x.criterion,
+            a: x.a,
+            b: x.b,
+            score: x.score,
+            elo_adj_a: x.elo_adj_a,
+            elo_adj_b: x.elo_adj_b,
+            criterion_adj_a: x.criterion_adj_a,
+            criterion_adj_b: x.criterion_adj_b,
+            time: timestamp(x.time),
+        })
+    }
+}
+
+impl CriticData for MemoryConnection {
+    fn next_contest(&self) -> Result<dto::Contest, DbError> {
+        let unrated = self.elo_config()?.baseline;
+        let cooldown_start = self.cooldown_start()?;
+        let rng = RandomState::new();
+        let pair = |a: i32, b: i32, x: i32, y: i32| (a == x && b == y) || (a == y && b == x);
+
+        let mut best: Option<(f32, u64, &RatingRow, &RatingRow, i32)> = None;
+        for tc1 in self.title_criteria.values() {
+            for tc2 in self.title_criteria.values() {
+                if tc1.key != tc2.key || tc1.title >= tc2.title {
+                    continue;
+                }
+
+                for (&criterion, _) in self.criteria.iter().filter(|(_, c)| c.group == tc1.key) {
+                    let played = self
+                        .match_history
+                        .values()
+                        .any(|m| m.criterion == criterion && pair(m.a, m.b, tc1.title, tc2.title));
+                    let skipped = self.skip_history.values().any(|s| {
+                        s.criterion == criterion
+                            && pair(s.a, s.b, tc1.title, tc2.title)
+                            && s.time > cooldown_start
+                    });
+                    let excluded = self.exclusions.values().any(|ex| {
+                        (ex.title == tc1.title || ex.title == tc2.title)
+                            && ex.criterion.is_none_or(|x| x == criterion)
+                    });
+                    if played || skipped || excluded {
+                        continue;
+                    }
+
+                    let elo = |title| {
+                        self.find_criterion_rating(title, criterion)
+                            .map_or(unrated, |x| x.rating.elo)
+                    };
+                    let distance = (elo(tc1.title) - elo(tc2.title)).abs();
+                    let roll = rng.hash_one((tc1.title, tc2.title, criterion));
+                    let closer = best
+                        .as_ref()
+                        .is_none_or(|(d, r, ..)| distance.total_cmp(d).then(roll.cmp(r)).is_lt());
+                    if closer {
+                        best = Some((distance, roll, tc1, tc2, criterion));
+                    }
+                }
+            }
+        }
+
+        let (_, _, tc1, tc2, criterion) = best.ok_or(DbError::NotFound)?;
+        let contestant = |tc: &RatingRow| dto::Contestant {
+            id: tc.title,
+            name: self.title_name(tc.title).to_string(),
+            elo: tc.rating.elo,
+            criterion_elo: self
+                .find_criterion_rating(tc.title, criterion)
+                .map_or(unrated, |x| x.rating.elo),
+        };
+        let row = &self.criteria[&criterion];
+        Ok(dto::Contest {
+            a: contestant(tc1),
+            b: contestant(tc2),
+            criterion: dto::Criterion {
+                group: row.group,
+                id: criterion,
+                name: row.name.clone(),
+                group_name: self.group_name(row.group).to_string(),
+            },
+        })
+    }
+
+    fn top(
+        &self,
+        criteria_group: &str,
+        count: usize,
+        page: usize,
+    ) -> Result<Vec<dto::TopRow>, DbError> {
+        let mut rows: Vec<&RatingRow> = self
+            .title_criteria
+            .values()
+            .filter(|x| criteria_group.is_empty() || self.group_name(x.key) == criteria_group)
+            .collect();
+        rows.sort_by(|a, b| {
+            self.group_name(a.key)
+                .cmp(self.group_name(b.key))
+                .then(b.rating.elo.total_cmp(&a.rating.elo))
+        });
+
+        let rows = rows
+            .into_iter()
+            .map(|x| {
+                top_row(
+                    self.group_name(x.key),
+                    self.title_name(x.title),
+                    x.rating.elo,
+                    x.rating.deviation,
+                    x.rating.matches,
+                )
+            })
+            .collect();
+        Ok(self::page(rows, count, page))
+    }
+
+    fn top_criterion(
+        &self,
+        criterion: i32,
+        count: usize,
+        page: usize,
+    ) -> Result<Vec<dto::TopRow>, DbError> {
+        let Some(row) = self.criteria.get(&criterion) else {
+            return Ok(vec![]);
+        };
+        let initial = self.elo_config()?.initial_rating();
+
+        let mut rows: Vec<(i32, dto::Rating)> = self
+            .title_criteria
+            .values()
+            .filter(|x| x.key == row.group)
+            .map(|x| {
+                let rating = self
+                    .find_criterion_rating(x.title, criterion)
+                    .map_or(initial, |x| x.rating);
+                (x.title, rating)
+            })
+            .collect();
+        rows.sort_by(|a, b| {
+            b.1.elo
+                .total_cmp(&a.1.elo)
+                .then(self.title_name(a.0).cmp(self.title_name(b.0)))
+        });
+
+        let group = self.group_name(row.group);
+        let rows = rows
+            .into_iter()
+            .map(|(title, x)| top_row(group, self.title_name(title), x.elo, x.deviation, x.matches))
+            .collect();
+        Ok(self::page(rows, count, page))
+    }
+
+    fn top_composite(
+        &self,
+        criteria_group: &str,
+        count: usize,
+        page: usize,
+    ) -> Result<Vec<dto::TopRow>, DbError> {
+        let initial = self.elo_config()?.initial_rating();
+
+        let mut rows: Vec<(&RatingRow, f64, f64)> = Vec::new();
+        for x in self.title_criteria.values() {
+            if !criteria_group.is_empty() && self.group_name(x.key) != criteria_group {
+                continue;
+            }
+
+            let (mut weights, mut elo, mut deviation) = (0.0, 0.0, 0.0);
+            let mut any = false;
+            for (&criterion, c) in self.criteria.iter().filter(|(_, c)| c.group == x.key) {
+                let rating = self
+                    .find_criterion_rating(x.title, criterion)
+                    .map_or(initial, |x| x.rating);
+                let weight = f64::from(c.weight);
+                weights += weight;
+                elo += weight * f64::from(rating.elo);
+                deviation += weight * f64::from(rating.deviation);
+                any = true;
+            }
+            // Groups without criteria have nothing to rank by.
+            if !any {
+                continue;
+            }
+
+            if weights == 0.0 {
+                rows.push((x, x.rating.elo.into(), x.rating.deviation.into()));
+            } else {
+                rows.push((x, elo / weights, deviation / weights));
+            }
+        }
+        rows.sort_by(|a, b| {
+            self.group_name(a.0.key)
+                .cmp(self.group_name(b.0.key))
+                .then(b.1.total_cmp(&a.1))
+        });
+
+        let rows = rows
+            .into_iter()
+            .map(|(x, score, deviation)| {
+                top_row(
+                    self.group_name(x.key),
+                    self.title_name(x.title),
+                    score as f32,
+                    deviation as f32,
+                    x.rating.matches,
+                )
+            })
+            .collect();
+        Ok(self::page(rows, count, page))
+    }
+
+    fn all_groups(&self) -> Result<Vec<dto::CriteriaGroup>, DbError> {
+        let mut results: Vec<dto::CriteriaGroup> = self
+            .criteria_group
+            .iter()
+            .map(|(id, name)| dto::CriteriaGroup {
+                id: *id,
+                name: name.clone(),
+            })
+            .collect();
+        results.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(results)
+    }
+
+    fn criteria(&self, id: i32) -> Result<Vec<dto::CriteriaGroupItem>, DbError> {
+        let mut results: Vec<dto::CriteriaGroupItem> = self
+            .criteria
+            .iter()
+            .filter(|(_, x)| x.group == id)
+            .map(|(id, x)| dto::CriteriaGroupItem {
+                id: *id,
+                name: x.name.clone(),
+                weight: x.weight,
+            })
+            .collect();
+        results.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(results)
+    }
+
+    fn all_titles(&self) -> Result<Vec<dto::Title>, DbError> {
+        let mut results: Vec<dto::Title> = self
+            .titles
+            .iter()
+            .map(|(id, name)| dto::Title {
+                id: *id,
+                name: name.clone(),
+            })
+            .collect();
+        results.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(results)
+    }
+
+    fn title_details(&self, title: i32) -> Result<dto::TitleDetails, DbError> {
+        let name = self.titles.get(&title).ok_or(DbError::NotFound)?;
+        let details = self.title_details.get(&title).cloned().unwrap_or_default();
+        let mut fields: Vec<dto::TitleField> = self
+            .title_fields
+            .values()
+            .filter(|x| x.title == title)
+            .map(|x| dto::TitleField {
+                key: x.key.clone(),
+                value: x.value.clone(),
+            })
+            .collect();
+        fields.sort_by(|a, b| a.key.cmp(&b.key));
+
+        Ok(dto::TitleDetails {
+            id: title,
+            name: name.clone(),
+            year: details.year,
+            platform: details.platform,
+            creator: details.creator,
+            notes: details.notes,
+            fields,
+        })
+    }
+
+    fn groups_by_title(&self, id: i32) -> Result<Vec<dto::CriteriaGroup>, DbError> {
+        let mut results: Vec<dto::CriteriaGroup> = self
+            .title_criteria
+            .values()
+            .filter(|x| x.title == id)
+            .map(|x| dto::CriteriaGroup {
+                id: x.key,
+                name: self.group_name(x.key).to_string(),
+            })
+            .collect();
+        results.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(results)
+    }
+
+    fn titles_in_group(&self, id: i32) -> Result<Vec<dto::Title>, DbError> {
+        let mut results: Vec<dto::Title> = self
+            .title_criteria
+            .values()
+            .filter(|x| x.key == id)
+            .map(|x| dto::Title {
+                id: x.title,
+                name: self.title_name(x.title).to_string(),
+            })
+            .collect();
+        results.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(results)
+    }
+
+    fn rating_system(&self) -> Result<dto::RatingSystem, DbError> {
+        Ok(self.settings.rating_system)
+    }
+
+    fn elo_config(&self) -> Result<elo::EloConfig, DbError> {
+        Ok(self.settings.elo_config.clone())
+    }
+
+    fn skip_cooldown(&self) -> Result<u32, DbError> {
+        Ok(self.settings.skip_cooldown)
+    }
+
+    fn contest_strategy(&self) -> Result<dto::ContestStrategy, DbError> {
+        Ok(self.settings.contest_strategy)
+    }
+
+    fn score_scale(&self) -> Result<dto::ScoreScale, DbError> {
+        Ok(self.settings.score_scale)
+    }
+
+    fn skips(&self) -> Result<Vec<dto::Skip>, DbError> {
+        let cooldown_start = self.cooldown_start()?;
+
+        let mut rows: Vec<_> = self
+            .skip_history
+            .values()
+            .filter(|x| x.time > cooldown_start)
+            .collect();
+        rows.sort_by_key(|x| x.time);
+
+        Ok(rows
+            .into_iter()
+            .map(|x| dto::Skip {
+                criterion: x.criterion,
+                a: x.a,
+                b: x.b,
+                reason: x.reason.clone(),
+                time: timestamp(x.time),
+            })
+            .collect())
+    }
+
+    fn matches(&self) -> Result<Vec<dto::MatchRecord>, DbError> {
+        let mut ids: Vec<i32> = self.match_history.keys().copied().collect();
+        ids.sort_by_key(|x| self.match_history[x].time);
+
+        Ok(ids
+            .into_iter()
+            .filter_map(|x| self.match_record(x))
+            .collect())
+    }
+
+    fn match_history(
+        &self,
+        title: Option<i32>,
+        criterion: Option<i32>,
+        count: usize,
+        page: usize,
+    ) -> Result<Vec<dto::MatchRecord>, DbError> {
+        let mut ids: Vec<i32> = self
+            .match_history
+            .iter()
+            .rev()
+            .filter(|(_, x)| title.is_none_or(|t| x.a == t || x.b == t))
+            .filter(|(_, x)| criterion.is_none_or(|c| x.criterion == c))
+            .map(|(id, _)| *id)
+            .collect();
+        ids.sort_by_key(|x| Reverse(self.match_history[x].time));
+
+        let rows = ids
+            .into_iter()
+            .filter_map(|x| self.match_record(x))
+            .collect();
+        Ok(self::page(rows, count, page))
+    }
+
+    fn ratings(&self) -> Result<Vec<dto::TitleRating>, DbError> {
+        let groups = self.title_criteria.values().map(|x| dto::TitleRating {
+            title: x.title,
+            group: x.key,
+            criterion: None,
+            rating: x.rating,
+        });
+        let criteria = self.title_criterion.values().filter_map(|x| {
+            Some(dto::TitleRating {
+                title: x.title,
+                group: self.criteria.get(&x.key)?.group,
+                criterion: Some(x.key),
+                rating: x.rating,
+            })
+        });
+
+        Ok(groups.chain(criteria).collect())
+    }
+
+    fn rating_history(&self, title: i32) -> Result<Vec<dto::RatingHistory>, DbError> {
+        Ok(replay::history(title, &self.ratings()?, &self.matches()?))
+    }
+
+    fn head_to_head(&self, a: i32, b: i32) -> Result<Vec<dto::HeadToHead>, DbError> {
+        head_to_head::compare(self, a, b)
+    }
+
+    fn exclusions(&self) -> Result<Vec<dto::Exclusion>, DbError> {
+        let mut results: Vec<dto::Exclusion> = self.exclusions.values().cloned().collect();
+        results.sort_by_key(|x| (x.title, x.criterion));
+
+        Ok(results)
+    }
+
+    fn active_tournament(&self) -> Result<Option<dto::Tournament>, DbError> {
+        let pending = |id: i32| {
+            self.tournament_pairing
+                .values()
+                .any(|p| p.tournament == id && p.b.is_some() && p.score.is_none())
+        };
+
+        Ok(self
+            .tournament
+            .iter()
+            .rev()
+            .find(|(id, x)| x.round < x.rounds || pending(**id))
+            .and_then(|(id, x)| {
+                Some(dto::Tournament {
+                    id: *id,
+                    group: self.criteria.get(&x.criterion)?.group,
+                    criterion: x.criterion,
+                    rounds: x.rounds,
+                    round: x.round,
+                })
+            }))
+    }
+
+    fn tournament_pairings(&self, tournament: i32) -> Result<Vec<dto::TournamentPairing>, DbError> {
+        let mut results: Vec<dto::TournamentPairing> = self
+            .tournament_pairing
+            .values()
+            .filter(|x| x.tournament == tournament)
+            .cloned()
+            .collect();
+        results.sort_by_key(|x| x.round);
+
+        Ok(results)
+    }
+}
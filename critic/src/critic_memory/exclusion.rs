@@ -0,0 +1,41 @@
+use crate::{
+    dto::{ExcludeTitle, Exclusion, IncludeTitle},
+    DbError, Record,
+};
+
+use super::{foreign_key, insert, MemoryConnection};
+
+impl Record<MemoryConnection> for ExcludeTitle {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        if connection
+            .exclusions
+            .values()
+            .any(|x| x.title == self.title && x.criterion == self.criterion)
+        {
+            return Ok(0);
+        }
+        foreign_key(connection.titles.contains_key(&self.title))?;
+        foreign_key(
+            self.criterion
+                .is_none_or(|x| connection.criteria.contains_key(&x)),
+        )?;
+
+        let row = Exclusion {
+            title: self.title,
+            criterion: self.criterion,
+        };
+        insert(&mut connection.exclusions, &mut connection.last_insert, row);
+        Ok(1)
+    }
+}
+
+impl Record<MemoryConnection> for IncludeTitle {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        let before = connection.exclusions.len();
+        connection
+            .exclusions
+            .retain(|_, x| x.title != self.title || x.criterion != self.criterion);
+
+        Ok(before - connection.exclusions.len())
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{
+    dto::{DeleteCriteriaGroup, GroupAddToTiles, NewCriteriaGroup, UpdateCriteriaGroup},
+    CriticData, DbError, Record,
+};
+
+use super::{insert, unique, MemoryConnection};
+
+impl Record<MemoryConnection> for UpdateCriteriaGroup {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        if !connection.criteria_group.contains_key(&self.id) {
+            return Ok(0);
+        }
+        if connection
+            .criteria_group
+            .iter()
+            .any(|(id, x)| *id != self.id && *x == self.name)
+        {
+            return Err(unique("criteria_group.value"));
+        }
+
+        connection.criteria_group.insert(self.id, self.name.clone());
+        Ok(1)
+    }
+}
+
+impl Record<MemoryConnection> for NewCriteriaGroup {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        if connection.criteria_group.values().any(|x| *x == self.name) {
+            return Err(unique("criteria_group.value"));
+        }
+
+        let id = insert(
+            &mut connection.criteria_group,
+            &mut connection.last_insert,
+            self.name.clone(),
+        );
+        Ok(id as usize)
+    }
+}
+
+impl Record<MemoryConnection> for DeleteCriteriaGroup {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        Ok(connection.delete_group(self.id))
+    }
+}
+
+impl Record<MemoryConnection> for GroupAddToTiles {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        let baseline = connection.elo_config()?.baseline;
+
+        connection.transaction(|conn| {
+            let missing: Vec<i32> = conn
+                .titles
+                .keys()
+                .copied()
+                .filter(|x| conn.find_rating(*x, self.id).is_none())
+                .collect();
+            for title in &missing {
+                conn.add_title_criteria(*title, self.id, baseline)?;
+            }
+
+            Ok(missing.len())
+        })
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{
+    dto::{DeleteMatch, FlipMatch, MatchResult, RatingSystem, Rejudge, UndoLastMatch},
+    replay, CriticData, DbError, Record,
+};
+
+use super::{foreign_key, insert, now, MatchRow, MemoryConnection, RatingRow};
+
+impl Record<MemoryConnection> for MatchResult {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        connection.transaction(|conn| add_match(conn, self).map(|_| 1))
+    }
+}
+
+/// Records `result` and moves both titles' ratings, returning the match id.
+pub(super) fn add_match(conn: &mut MemoryConnection, result: &MatchResult) -> Result<i32, DbError> {
+    let config = conn.elo_config()?;
+    let model = conn.rating_system()?.model(&config);
+    let initial = config.initial_rating();
+
+    let rating = |conn: &MemoryConnection, title| {
+        conn.find_rating(title, result.criteria_group)
+            .map_or(initial, |x| x.rating)
+    };
+    let criterion_rating = |conn: &MemoryConnection, title| {
+        conn.find_criterion_rating(title, result.criterion)
+            .map_or(initial, |x| x.rating)
+    };
+    let group = (rating(conn, result.a), rating(conn, result.b));
+    let criterion = (
+        criterion_rating(conn, result.a),
+        criterion_rating(conn, result.b),
+    );
+
+    let new_group = model.calc_change(group.0, group.1, result.score);
+    let new_group = (new_group.0.played(), new_group.1.played());
+    let new_criterion = model.calc_change(criterion.0, criterion.1, result.score);
+    let new_criterion = (new_criterion.0.played(), new_criterion.1.played());
+
+    foreign_key(conn.criteria.contains_key(&result.criterion))?;
+    foreign_key(conn.titles.contains_key(&result.a))?;
+    foreign_key(conn.titles.contains_key(&result.b))?;
+    let row = MatchRow {
+        criterion: result.criterion,
+        a: result.a,
+        b: result.b,
+        score: result.score,
+        elo_adj_a: new_group.0.elo - group.0.elo,
+        elo_adj_b: new_group.1.elo - group.1.elo,
+        criterion_adj_a: new_criterion.0.elo - criterion.0.elo,
+        criterion_adj_b: new_criterion.1.elo - criterion.1.elo,
+        pairing: None,
+        time: now(),
+    };
+    let match_id = insert(&mut conn.match_history, &mut conn.last_insert, row);
+
+    for (id, rating) in [(result.a, new_group.0), (result.b, new_group.1)] {
+        let row = conn
+            .title_criteria
+            .values_mut()
+            .find(|x| x.title == id && x.key == result.criteria_group);
+        if let Some(row) = row {
+            row.rating = rating;
+        }
+    }
+
+    for (id, rating) in [(result.a, new_criterion.0), (result.b, new_criterion.1)] {
+        conn.set_criterion_rating(id, result.criterion, rating)?;
+    }
+
+    Ok(match_id)
+}
+
+impl Record<MemoryConnection> for UndoLastMatch {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        let last = connection
+            .match_history
+            .iter()
+            .max_by_key(|(id, x)| (x.time, **id))
+            .map(|(id, x)| (*id, x.clone()));
+        let Some((id, last)) = last else {
+            return Ok(0);
+        };
+        if connection.rating_system()? != RatingSystem::Elo {
+            return connection.transaction(|conn| {
+                conn.unscore_pairing(&last);
+                conn.match_history.remove(&id);
+                replay::recompute(conn)?;
+                Ok(1)
+            });
+        }
+        let group = connection.criteria.get(&last.criterion).map(|x| x.group);
+
+        let revert = |row: &mut RatingRow, adj: f32| {
+            row.rating.elo -= adj;
+            row.rating.matches = (row.rating.matches - 1).max(0);
+        };
+        for (title, adj, criterion_adj) in [
+            (last.a, last.elo_adj_a, last.criterion_adj_a),
+            (last.b, last.elo_adj_b, last.criterion_adj_b),
+        ] {
+            let row = connection
+                .title_criteria
+                .values_mut()
+                .find(|x| x.title == title && Some(x.key) == group);
+            if let Some(row) = row {
+                revert(row, adj);
+            }
+
+            let row = connection
+                .title_criterion
+                .values_mut()
+                .find(|x| x.title == title && x.key == last.criterion);
+            if let Some(row) = row {
+                revert(row, criterion_adj);
+            }
+        }
+
+        connection.unscore_pairing(&last);
+        connection.match_history.remove(&id);
+        Ok(1)
+    }
+}
+
+impl Record<MemoryConnection> for DeleteMatch {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        connection.transaction(|conn| {
+            if conn.match_history.remove(&self.id).is_none() {
+                return Ok(0);
+            }
+
+            replay::recompute(conn)?;
+            Ok(1)
+        })
+    }
+}
+
+impl Record<MemoryConnection> for FlipMatch {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        connection.transaction(|conn| {
+            let Some(row) = conn.match_history.get_mut(&self.id) else {
+                return Ok(0);
+            };
+            row.score = 1.0 - row.score;
+
+            replay::recompute(conn)?;
+            Ok(1)
+        })
+    }
+}
+
+impl Record<MemoryConnection> for Rejudge {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        connection.transaction(|conn| {
+            let before = conn.match_history.len();
+            conn.match_history.retain(|_, x| {
+                x.criterion != self.criterion
+                    || !((x.a == self.a && x.b == self.b) || (x.a == self.b && x.b == self.a))
+            });
+            let deleted = before - conn.match_history.len();
+
+            replay::recompute(conn)?;
+            MatchResult {
+                criteria_group: self.criteria_group,
+                criterion: self.criterion,
+                a: self.a,
+                b: self.b,
+                score: self.score,
+            }
+            .save(conn)?;
+            Ok(deleted)
+        })
+    }
+}
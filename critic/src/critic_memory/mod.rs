@@ -0,0 +1,699 @@
+mod connection;
+mod criterion;
+mod critic_data;
+mod exclusion;
+mod group_criteria;
+mod match_result;
+mod new_criterion_item;
+mod rating;
+mod settings;
+mod skip;
+mod title;
+mod tournament;
+
+use std::collections::BTreeMap;
+
+use crate::{
+    dto::{
+        ContestStrategy, Exclusion, Rating, RatingSystem, ScoreScale, TournamentPairing,
+        DEFAULT_SKIP_COOLDOWN,
+    },
+    elo::EloConfig,
+    DbError,
+};
+
+/// Storage kept entirely in memory, with the same tables and behaviour as
+/// the SQLite backend. Nothing is written to disk.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryConnection {
+    titles: BTreeMap<i32, String>,
+    /// Typed metadata, keyed by title.
+    title_details: BTreeMap<i32, DetailsRow>,
+    title_fields: BTreeMap<i32, FieldRow>,
+    criteria_group: BTreeMap<i32, String>,
+    criteria: BTreeMap<i32, CriterionRow>,
+    /// Ratings for a whole group, keyed by group.
+    title_criteria: BTreeMap<i32, RatingRow>,
+    /// Ratings for a single criterion, keyed by criterion.
+    title_criterion: BTreeMap<i32, RatingRow>,
+    match_history: BTreeMap<i32, MatchRow>,
+    skip_history: BTreeMap<i32, SkipRow>,
+    exclusions: BTreeMap<i32, Exclusion>,
+    tournament: BTreeMap<i32, TournamentRow>,
+    tournament_pairing: BTreeMap<i32, TournamentPairing>,
+    settings: Settings,
+    /// Id of the most recently inserted row, like SQLite's
+    /// `last_insert_rowid`.
+    last_insert: i32,
+}
+
+#[derive(Debug, Default, Clone)]
+struct DetailsRow {
+    year: Option<i32>,
+    platform: Option<String>,
+    creator: Option<String>,
+    notes: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct FieldRow {
+    title: i32,
+    key: String,
+    value: String,
+}
+
+#[derive(Debug, Clone)]
+struct CriterionRow {
+    group: i32,
+    name: String,
+    weight: f32,
+}
+
+#[derive(Debug, Clone)]
+struct RatingRow {
+    title: i32,
+    /// Group or criterion, depending on the table.
+    key: i32,
+    rating: Rating,
+}
+
+#[derive(Debug, Clone)]
+struct MatchRow {
+    criterion: i32,
+    a: i32,
+    b: i32,
+    score: f32,
+    elo_adj_a: f32,
+    elo_adj_b: f32,
+    criterion_adj_a: f32,
+    criterion_adj_b: f32,
+    /// Tournament pairing the match scored, if any.
+    pairing: Option<i32>,
+    /// Seconds since the Unix epoch.
+    time: u64,
+}
+
+#[derive(Debug, Clone)]
+struct SkipRow {
+    criterion: i32,
+    a: i32,
+    b: i32,
+    reason: Option<String>,
+    /// Seconds since the Unix epoch.
+    time: u64,
+}
+
+#[derive(Debug, Clone)]
+struct TournamentRow {
+    criterion: i32,
+    rounds: i32,
+    round: i32,
+}
+
+#[derive(Debug, Clone)]
+struct Settings {
+    rating_system: RatingSystem,
+    skip_cooldown: u32,
+    contest_strategy: ContestStrategy,
+    score_scale: ScoreScale,
+    elo_config: EloConfig,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            rating_system: RatingSystem::default(),
+            skip_cooldown: DEFAULT_SKIP_COOLDOWN,
+            contest_strategy: ContestStrategy::default(),
+            score_scale: ScoreScale::default(),
+            elo_config: EloConfig::default(),
+        }
+    }
+}
+
+impl MemoryConnection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `f`, putting every table back as it was when it fails.
+    fn transaction<R>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<R, DbError>,
+    ) -> Result<R, DbError> {
+        let before = self.clone();
+        let result = f(self);
+        if result.is_err() {
+            *self = before;
+        }
+        result
+    }
+
+    fn find_rating(&self, title: i32, group: i32) -> Option<&RatingRow> {
+        find(&self.title_criteria, title, group)
+    }
+
+    fn find_criterion_rating(&self, title: i32, criterion: i32) -> Option<&RatingRow> {
+        find(&self.title_criterion, title, criterion)
+    }
+
+    fn add_title_criteria(&mut self, title: i32, group: i32, elo: f32) -> Result<i32, DbError> {
+        foreign_key(self.titles.contains_key(&title))?;
+        foreign_key(self.criteria_group.contains_key(&group))?;
+        if self.find_rating(title, group).is_some() {
+            return Err(unique("title_criteria.title_id, title_criteria.group_id"));
+        }
+
+        let row = RatingRow {
+            title,
+            key: group,
+            rating: Rating {
+                elo,
+                ..Default::default()
+            },
+        };
+        Ok(insert(&mut self.title_criteria, &mut self.last_insert, row))
+    }
+
+    /// Overwrites a title's rating for a criterion, adding it when missing.
+    fn set_criterion_rating(
+        &mut self,
+        title: i32,
+        criterion: i32,
+        rating: Rating,
+    ) -> Result<(), DbError> {
+        let existing = self
+            .title_criterion
+            .values_mut()
+            .find(|x| x.title == title && x.key == criterion);
+        if let Some(row) = existing {
+            row.rating = rating;
+            return Ok(());
+        }
+
+        foreign_key(self.titles.contains_key(&title))?;
+        foreign_key(self.criteria.contains_key(&criterion))?;
+        let row = RatingRow {
+            title,
+            key: criterion,
+            rating,
+        };
+        insert(&mut self.title_criterion, &mut self.last_insert, row);
+        Ok(())
+    }
+
+    /// Deletes a title and every row referring to it.
+    fn delete_title(&mut self, id: i32) -> usize {
+        if self.titles.remove(&id).is_none() {
+            return 0;
+        }
+
+        self.title_details.remove(&id);
+        self.title_fields.retain(|_, x| x.title != id);
+        self.title_criteria.retain(|_, x| x.title != id);
+        self.title_criterion.retain(|_, x| x.title != id);
+        self.match_history.retain(|_, x| x.a != id && x.b != id);
+        self.skip_history.retain(|_, x| x.a != id && x.b != id);
+        self.exclusions.retain(|_, x| x.title != id);
+        self.tournament_pairing
+            .retain(|_, x| x.a != id && x.b != Some(id));
+        self.unlink_pairings();
+        1
+    }
+
+    /// Deletes a group with its criteria and every row referring to them.
+    fn delete_group(&mut self, id: i32) -> usize {
+        if self.criteria_group.remove(&id).is_none() {
+            return 0;
+        }
+
+        let criteria: Vec<i32> = self
+            .criteria
+            .iter()
+            .filter(|(_, x)| x.group == id)
+            .map(|(id, _)| *id)
+            .collect();
+        for criterion in criteria {
+            self.delete_criterion(criterion);
+        }
+        self.title_criteria.retain(|_, x| x.key != id);
+        1
+    }
+
+    /// Deletes a criterion and every row referring to it.
+    fn delete_criterion(&mut self, id: i32) -> usize {
+        if self.criteria.remove(&id).is_none() {
+            return 0;
+        }
+
+        self.title_criterion.retain(|_, x| x.key != id);
+        self.match_history.retain(|_, x| x.criterion != id);
+        self.skip_history.retain(|_, x| x.criterion != id);
+        self.exclusions.retain(|_, x| x.criterion != Some(id));
+        let tournaments: Vec<i32> = self
+            .tournament
+            .iter()
+            .filter(|(_, x)| x.criterion == id)
+            .map(|(id, _)| *id)
+            .collect();
+        for tournament in tournaments {
+            self.delete_tournament(tournament);
+        }
+        1
+    }
+
+    fn delete_tournament(&mut self, id: i32) -> usize {
+        if self.tournament.remove(&id).is_none() {
+            return 0;
+        }
+
+        self.tournament_pairing.retain(|_, x| x.tournament != id);
+        self.unlink_pairings();
+        1
+    }
+
+    /// Forgets pairings that no longer exist, like `ON DELETE SET NULL`.
+    fn unlink_pairings(&mut self) {
+        for row in self.match_history.values_mut() {
+            if row
+                .pairing
+                .is_some_and(|x| !self.tournament_pairing.contains_key(&x))
+            {
+                row.pairing = None;
+            }
+        }
+    }
+
+    /// Clears the score the match `row` gave its tournament pairing.
+    fn unscore_pairing(&mut self, row: &MatchRow) {
+        if let Some(pairing) = row
+            .pairing
+            .and_then(|x| self.tournament_pairing.get_mut(&x))
+        {
+            pairing.score = None;
+        }
+    }
+}
+
+fn find(table: &BTreeMap<i32, RatingRow>, title: i32, key: i32) -> Option<&RatingRow> {
+    table.values().find(|x| x.title == title && x.key == key)
+}
+
+/// Adds `row` under the id SQLite would give it: one past the largest in
+/// use.
+fn insert<V>(table: &mut BTreeMap<i32, V>, last_insert: &mut i32, row: V) -> i32 {
+    let id = table.keys().next_back().map_or(1, |x| x + 1);
+    table.insert(id, row);
+    *last_insert = id;
+    id
+}
+
+fn foreign_key(exists: bool) -> Result<(), DbError> {
+    if exists {
+        Ok(())
+    } else {
+        Err(DbError::Constraint("FOREIGN KEY constraint failed".into()))
+    }
+}
+
+fn unique(columns: &str) -> DbError {
+    DbError::Constraint(format!("UNIQUE constraint failed: {columns}"))
+}
+
+/// Seconds since the Unix epoch.
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs())
+}
+
+/// Formats `secs` like SQLite's `CURRENT_TIMESTAMP`, e.g.
+/// `2024-05-01 18:30:00`.
+fn timestamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+
+    // Civil date from days since the epoch, after Howard Hinnant's
+    // `civil_from_days`.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        rem / 3_600,
+        rem / 60 % 60,
+        rem % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dto::{ExcludeTitle, MatchResult, NewCriteriaGroup, NewCriterion, NewTitle},
+        CriticData, DbConnection,
+    };
+
+    #[test]
+    fn failed_writes_change_nothing() {
+        let mut conn = MemoryConnection::new();
+        conn.save(&NewTitle { name: "A".into() }).unwrap();
+        conn.save(&NewCriteriaGroup {
+            name: "Group".into(),
+        })
+        .unwrap();
+        conn.save(&NewCriterion {
+            group: 1,
+            name: "Criterion".into(),
+        })
+        .unwrap();
+
+        let result = conn.save(&MatchResult {
+            criteria_group: 1,
+            criterion: 1,
+            a: 1,
+            b: 2,
+            score: 1.0,
+        });
+        assert!(matches!(result, Err(DbError::Constraint(_))));
+        assert!(conn.matches().unwrap().is_empty());
+        assert!(conn.ratings().unwrap().is_empty());
+
+        let result = conn.save(&ExcludeTitle {
+            title: 1,
+            criterion: Some(2),
+        });
+        assert!(matches!(result, Err(DbError::Constraint(_))));
+        assert!(matches!(conn.next_contest(), Err(DbError::NotFound)));
+    }
+
+    #[test]
+    fn formats_timestamps_like_sqlite() {
+        assert_eq!(timestamp(0), "1970-01-01 00:00:00");
+        assert_eq!(timestamp(951_782_400), "2000-02-29 00:00:00");
+        assert_eq!(timestamp(1_714_588_245), "2024-05-01 18:30:45");
+    }
+}
+
+/// Runs the same script against both backends and compares everything they
+/// report.
+#[cfg(all(test, feature = "rusqlite"))]
+mod cross_check {
+    use super::MemoryConnection;
+    use crate::{critic_sqlite::Connection, dto::*, CriticData, DbConnection, DbError, Record};
+
+    fn outcome(result: Result<usize, DbError>) -> String {
+        match result {
+            Ok(x) => format!("Ok({x})"),
+            Err(DbError::Constraint(_)) => "Constraint".to_string(),
+            Err(err) => format!("{err:?}"),
+        }
+    }
+
+    /// Everything readable, with times left out since the backends may
+    /// straddle a second.
+    fn snapshot<T: CriticData>(conn: &T) -> Vec<String> {
+        let mut matches = conn.matches().unwrap();
+        let mut page = conn.match_history(Some(1), None, 2, 0).unwrap();
+        for x in matches.iter_mut().chain(page.iter_mut()) {
+            assert_eq!(x.time.len(), 19);
+            x.time.clear();
+        }
+        let skips: Vec<_> = conn
+            .skips()
+            .unwrap()
+            .into_iter()
+            .map(|x| (x.criterion, x.a, x.b, x.reason))
+            .collect();
+
+        let mut log = vec![
+            format!("{:?}", conn.all_titles().unwrap()),
+            format!("{:?}", conn.all_groups().unwrap()),
+            format!("{:?}", conn.criteria(1).unwrap()),
+            format!("{:?}", conn.groups_by_title(1).unwrap()),
+            format!("{:?}", conn.titles_in_group(1).unwrap()),
+            format!("{:?}", conn.ratings().unwrap()),
+            format!("{matches:?}"),
+            format!("{page:?}"),
+            format!("{:?}", conn.top("", 10, 0).unwrap()),
+            format!("{:?}", conn.top("Group", 1, 1).unwrap()),
+            format!("{:?}", conn.top_criterion(1, 10, 0).unwrap()),
+            format!("{:?}", conn.top_composite("Group", 10, 0).unwrap()),
+            format!("{:?}", conn.exclusions().unwrap()),
+            format!("{skips:?}"),
+            format!("{:?}", conn.active_tournament().unwrap()),
+            format!("{:?}", conn.tournament_pairings(1).unwrap()),
+            format!("{:?}", conn.rating_history(1).unwrap()),
+            format!("{:?}", conn.head_to_head(1, 2).unwrap()),
+            format!("{:?}", conn.rating_system().unwrap()),
+            format!("{:?}", conn.skip_cooldown().unwrap()),
+        ];
+        for title in [1, 3] {
+            log.push(match conn.title_details(title) {
+                Ok(x) => format!("{x:?}"),
+                Err(err) => format!("{err:?}"),
+            });
+        }
+        log.push(match conn.next_contest() {
+            Ok(x) => format!("{x:?}"),
+            Err(err) => format!("{err:?}"),
+        });
+        log
+    }
+
+    fn script<T>(conn: &mut T) -> Vec<String>
+    where
+        T: DbConnection + CriticData,
+        NewTitle: Record<T>,
+        UpdateTitle: Record<T>,
+        DeleteTitle: Record<T>,
+        UpdateTitleDetails: Record<T>,
+        SetTitleField: Record<T>,
+        DeleteTitleField: Record<T>,
+        NewCriteriaGroup: Record<T>,
+        UpdateCriteriaGroup: Record<T>,
+        GroupAddToTiles: Record<T>,
+        NewCriterion: Record<T>,
+        UpdateCriterionWeight: Record<T>,
+        DeleteCriterion: Record<T>,
+        NewCategoryItem: Record<T>,
+        ExcludeTitle: Record<T>,
+        IncludeTitle: Record<T>,
+        MatchResult: Record<T>,
+        UndoLastMatch: Record<T>,
+        FlipMatch: Record<T>,
+        DeleteMatch: Record<T>,
+        Rejudge: Record<T>,
+        SkipContest: Record<T>,
+        SetRatingSystem: Record<T>,
+        NewTournament: Record<T>,
+        NewRound: Record<T>,
+        ScorePairing: Record<T>,
+        ScoreTournamentMatch: Record<T>,
+    {
+        let mut log = Vec::new();
+        let step = |log: &mut Vec<String>, result| log.push(outcome(result));
+
+        for name in ["Zelda", "Metroid", "Kirby", "Zelda"] {
+            step(&mut log, conn.save(&NewTitle { name: name.into() }));
+        }
+        for name in ["Group", "Group"] {
+            step(&mut log, conn.save(&NewCriteriaGroup { name: name.into() }));
+        }
+        for name in ["Story", "Music", "Story"] {
+            step(
+                &mut log,
+                conn.save(&NewCriterion {
+                    group: 1,
+                    name: name.into(),
+                }),
+            );
+        }
+        step(&mut log, conn.save(&GroupAddToTiles { id: 1 }));
+        step(&mut log, conn.save(&GroupAddToTiles { id: 1 }));
+        step(
+            &mut log,
+            conn.save(&UpdateCriterionWeight { id: 2, weight: 3.0 }),
+        );
+
+        // Leaves a single pair for `next_contest` to choose.
+        for (title, criterion) in [(3, None), (2, Some(2)), (3, None)] {
+            step(&mut log, conn.save(&ExcludeTitle { title, criterion }));
+        }
+        log.extend(snapshot(conn));
+        step(
+            &mut log,
+            conn.save(&IncludeTitle {
+                title: 3,
+                criterion: None,
+            }),
+        );
+
+        let judge = |criterion, a, b, score| MatchResult {
+            criteria_group: 1,
+            criterion,
+            a,
+            b,
+            score,
+        };
+        for (criterion, a, b, score) in [
+            (1, 1, 2, 1.0),
+            (1, 2, 3, 0.75),
+            (2, 3, 1, 0.5),
+            (1, 1, 3, 1.0),
+            (2, 2, 1, 0.25),
+            (1, 1, 4, 1.0),
+        ] {
+            step(&mut log, conn.save(&judge(criterion, a, b, score)));
+        }
+        step(&mut log, conn.save(&UndoLastMatch));
+        log.extend(snapshot(conn));
+
+        step(&mut log, conn.save(&FlipMatch { id: 2 }));
+        step(&mut log, conn.save(&DeleteMatch { id: 1 }));
+        step(&mut log, conn.save(&DeleteMatch { id: 1 }));
+        step(
+            &mut log,
+            conn.save(&Rejudge {
+                criteria_group: 1,
+                criterion: 1,
+                a: 1,
+                b: 3,
+                score: 0.0,
+            }),
+        );
+        step(
+            &mut log,
+            conn.save(&SetRatingSystem {
+                system: RatingSystem::Glicko2,
+            }),
+        );
+        step(&mut log, conn.save(&judge(2, 1, 3, 0.75)));
+        step(
+            &mut log,
+            conn.save(&SkipContest {
+                criterion: 1,
+                a: 2,
+                b: 1,
+                reason: Some("Not played".into()),
+            }),
+        );
+        log.extend(snapshot(conn));
+
+        step(
+            &mut log,
+            conn.save(&NewTournament {
+                criterion: 1,
+                rounds: 2,
+            }),
+        );
+        step(
+            &mut log,
+            conn.save(&NewRound {
+                tournament: 1,
+                round: 1,
+                pairings: vec![(1, Some(2)), (3, None)],
+            }),
+        );
+        step(
+            &mut log,
+            conn.save(&NewRound {
+                tournament: 1,
+                round: 2,
+                pairings: vec![(1, Some(9))],
+            }),
+        );
+        step(&mut log, conn.save(&ScorePairing { id: 1, score: 1.0 }));
+        for pairing in [99, 1] {
+            step(
+                &mut log,
+                conn.save(&ScoreTournamentMatch {
+                    pairing,
+                    criteria_group: 1,
+                    criterion: 1,
+                    a: 1,
+                    b: 2,
+                    score: 0.0,
+                }),
+            );
+        }
+        log.extend(snapshot(conn));
+        step(&mut log, conn.save(&UndoLastMatch));
+        log.extend(snapshot(conn));
+
+        step(
+            &mut log,
+            conn.save(&UpdateTitle {
+                id: 2,
+                name: "Zelda".into(),
+            }),
+        );
+        step(
+            &mut log,
+            conn.save(&UpdateCriteriaGroup {
+                id: 1,
+                name: "Games".into(),
+            }),
+        );
+        step(
+            &mut log,
+            conn.save(&NewCategoryItem {
+                name: "Mario".into(),
+                sub_categories: vec![],
+            }),
+        );
+        for title in [3, 99] {
+            step(
+                &mut log,
+                conn.save(&UpdateTitleDetails {
+                    title,
+                    year: Some(1992),
+                    creator: Some("HAL".into()),
+                    ..Default::default()
+                }),
+            );
+        }
+        for (title, key, value) in [(3, "igdb", "1"), (3, "igdb", "2"), (1, "imdb", "3")] {
+            step(
+                &mut log,
+                conn.save(&SetTitleField {
+                    title,
+                    key: key.into(),
+                    value: value.into(),
+                }),
+            );
+        }
+        step(
+            &mut log,
+            conn.save(&DeleteTitleField {
+                title: 1,
+                key: "imdb".into(),
+            }),
+        );
+        log.extend(snapshot(conn));
+
+        step(&mut log, conn.save(&DeleteCriterion { id: 2 }));
+        log.push(conn.save(&DeleteTitle { id: 3 }).is_ok().to_string());
+        log.extend(snapshot(conn));
+
+        log
+    }
+
+    #[test]
+    fn matches_sqlite() {
+        let expected = script(&mut Connection::open_category(":memory:").unwrap());
+        let actual = script(&mut MemoryConnection::new());
+
+        assert_eq!(expected.len(), actual.len());
+        for (expected, actual) in expected.iter().zip(&actual) {
+            assert_eq!(expected, actual);
+        }
+    }
+}
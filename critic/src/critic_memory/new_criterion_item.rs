@@ -0,0 +1,11 @@
+use crate::{dto::NewCategoryItem, DbError, Record};
+
+use super::{foreign_key, MemoryConnection};
+
+impl Record<MemoryConnection> for NewCategoryItem {
+    /// Matches the SQLite record, which stores the title's name where its id
+    /// belongs, so the first group fails its foreign key and nothing is kept.
+    fn save(&self, _connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        foreign_key(false).map(|_| 1)
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{dto::UpdateRatings, DbError, Record};
+
+use super::MemoryConnection;
+
+impl Record<MemoryConnection> for UpdateRatings {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        connection.transaction(|conn| {
+            for x in &self.ratings {
+                match x.criterion {
+                    Some(criterion) => conn.set_criterion_rating(x.title, criterion, x.rating)?,
+                    None => {
+                        let row = conn
+                            .title_criteria
+                            .values_mut()
+                            .find(|r| r.title == x.title && r.key == x.group);
+                        if let Some(row) = row {
+                            row.rating = x.rating;
+                        }
+                    }
+                }
+            }
+
+            for x in &self.adjustments {
+                if let Some(row) = conn.match_history.get_mut(&x.id) {
+                    row.elo_adj_a = x.elo_adj_a;
+                    row.elo_adj_b = x.elo_adj_b;
+                    row.criterion_adj_a = x.criterion_adj_a;
+                    row.criterion_adj_b = x.criterion_adj_b;
+                }
+            }
+
+            Ok(self.ratings.len())
+        })
+    }
+}
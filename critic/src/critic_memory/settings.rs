@@ -0,0 +1,41 @@
+use crate::{
+    dto::{SetContestStrategy, SetEloConfig, SetRatingSystem, SetScoreScale, SetSkipCooldown},
+    DbError, Record,
+};
+
+use super::MemoryConnection;
+
+impl Record<MemoryConnection> for SetRatingSystem {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        connection.settings.rating_system = self.system;
+        Ok(1)
+    }
+}
+
+impl Record<MemoryConnection> for SetSkipCooldown {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        connection.settings.skip_cooldown = self.hours;
+        Ok(1)
+    }
+}
+
+impl Record<MemoryConnection> for SetContestStrategy {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        connection.settings.contest_strategy = self.strategy;
+        Ok(1)
+    }
+}
+
+impl Record<MemoryConnection> for SetScoreScale {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        connection.settings.score_scale = self.scale;
+        Ok(1)
+    }
+}
+
+impl Record<MemoryConnection> for SetEloConfig {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        connection.settings.elo_config = self.config.clone();
+        Ok(1)
+    }
+}
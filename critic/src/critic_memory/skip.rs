@@ -0,0 +1,25 @@
+use crate::{dto::SkipContest, DbError, Record};
+
+use super::{foreign_key, insert, now, MemoryConnection, SkipRow};
+
+impl Record<MemoryConnection> for SkipContest {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        foreign_key(connection.criteria.contains_key(&self.criterion))?;
+        foreign_key(connection.titles.contains_key(&self.a))?;
+        foreign_key(connection.titles.contains_key(&self.b))?;
+
+        let row = SkipRow {
+            criterion: self.criterion,
+            a: self.a,
+            b: self.b,
+            reason: self.reason.clone(),
+            time: now(),
+        };
+        insert(
+            &mut connection.skip_history,
+            &mut connection.last_insert,
+            row,
+        );
+        Ok(1)
+    }
+}
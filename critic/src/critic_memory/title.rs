@@ -0,0 +1,122 @@
+use crate::{
+    dto::{
+        DeleteTitle, DeleteTitleCriteria, DeleteTitleField, NewTitle, NewTitleCriteria,
+        SetTitleField, UpdateTitle, UpdateTitleDetails,
+    },
+    CriticData, DbError, Record,
+};
+
+use super::{foreign_key, insert, unique, DetailsRow, FieldRow, MemoryConnection};
+
+impl Record<MemoryConnection> for NewTitle {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        // Like the SQLite backend, an existing name is left as it is.
+        if !connection.titles.values().any(|x| *x == self.name) {
+            insert(
+                &mut connection.titles,
+                &mut connection.last_insert,
+                self.name.clone(),
+            );
+        }
+
+        Ok(connection.last_insert as usize)
+    }
+}
+
+impl Record<MemoryConnection> for UpdateTitle {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        if !connection.titles.contains_key(&self.id) {
+            return Ok(1);
+        }
+        if connection
+            .titles
+            .iter()
+            .any(|(id, x)| *id != self.id && *x == self.name)
+        {
+            return Err(unique("titles.name"));
+        }
+
+        connection.titles.insert(self.id, self.name.clone());
+        Ok(1)
+    }
+}
+
+impl Record<MemoryConnection> for NewTitleCriteria {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        let baseline = connection.elo_config()?.baseline;
+
+        connection
+            .add_title_criteria(self.title, self.criteria, baseline)
+            .map(|x| x as usize)
+    }
+}
+
+impl Record<MemoryConnection> for DeleteTitle {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        connection.delete_title(self.id);
+
+        Ok(connection.last_insert as usize)
+    }
+}
+
+impl Record<MemoryConnection> for DeleteTitleCriteria {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        connection
+            .title_criteria
+            .retain(|_, x| x.title != self.title || x.key != self.criteria);
+
+        Ok(connection.last_insert as usize)
+    }
+}
+
+impl Record<MemoryConnection> for UpdateTitleDetails {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        foreign_key(connection.titles.contains_key(&self.title))?;
+
+        let row = DetailsRow {
+            year: self.year,
+            platform: self.platform.clone(),
+            creator: self.creator.clone(),
+            notes: self.notes.clone(),
+        };
+        connection.title_details.insert(self.title, row);
+        Ok(1)
+    }
+}
+
+impl Record<MemoryConnection> for SetTitleField {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        if let Some(row) = connection
+            .title_fields
+            .values_mut()
+            .find(|x| x.title == self.title && x.key == self.key)
+        {
+            row.value.clone_from(&self.value);
+            return Ok(1);
+        }
+        foreign_key(connection.titles.contains_key(&self.title))?;
+
+        let row = FieldRow {
+            title: self.title,
+            key: self.key.clone(),
+            value: self.value.clone(),
+        };
+        insert(
+            &mut connection.title_fields,
+            &mut connection.last_insert,
+            row,
+        );
+        Ok(1)
+    }
+}
+
+impl Record<MemoryConnection> for DeleteTitleField {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        let before = connection.title_fields.len();
+        connection
+            .title_fields
+            .retain(|_, x| x.title != self.title || x.key != self.key);
+
+        Ok(before - connection.title_fields.len())
+    }
+}
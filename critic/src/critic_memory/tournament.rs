@@ -0,0 +1,98 @@
+use crate::{
+    dto::{
+        DeleteTournament, MatchResult, NewRound, NewTournament, ScorePairing, ScoreTournamentMatch,
+        TournamentPairing,
+    },
+    DbError, Record,
+};
+
+use super::{foreign_key, insert, match_result, MemoryConnection, TournamentRow};
+
+impl Record<MemoryConnection> for NewTournament {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        foreign_key(connection.criteria.contains_key(&self.criterion))?;
+
+        let row = TournamentRow {
+            criterion: self.criterion,
+            rounds: self.rounds,
+            round: 0,
+        };
+        let id = insert(&mut connection.tournament, &mut connection.last_insert, row);
+        Ok(id as usize)
+    }
+}
+
+impl Record<MemoryConnection> for DeleteTournament {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        Ok(connection.delete_tournament(self.id))
+    }
+}
+
+impl Record<MemoryConnection> for NewRound {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        connection.transaction(|conn| {
+            for (a, b) in &self.pairings {
+                foreign_key(conn.tournament.contains_key(&self.tournament))?;
+                foreign_key(conn.titles.contains_key(a))?;
+                foreign_key(b.is_none_or(|x| conn.titles.contains_key(&x)))?;
+
+                let row = TournamentPairing {
+                    id: 0,
+                    tournament: self.tournament,
+                    round: self.round,
+                    a: *a,
+                    b: *b,
+                    score: None,
+                };
+                let id = insert(&mut conn.tournament_pairing, &mut conn.last_insert, row);
+                if let Some(row) = conn.tournament_pairing.get_mut(&id) {
+                    row.id = id;
+                }
+            }
+
+            if let Some(row) = conn.tournament.get_mut(&self.tournament) {
+                row.round = self.round;
+            }
+
+            Ok(self.pairings.len())
+        })
+    }
+}
+
+impl Record<MemoryConnection> for ScorePairing {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        match connection.tournament_pairing.get_mut(&self.id) {
+            Some(row) => {
+                row.score = Some(self.score);
+                Ok(1)
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+impl Record<MemoryConnection> for ScoreTournamentMatch {
+    fn save(&self, connection: &mut MemoryConnection) -> Result<usize, DbError> {
+        connection.transaction(|conn| {
+            let id = match_result::add_match(
+                conn,
+                &MatchResult {
+                    criteria_group: self.criteria_group,
+                    criterion: self.criterion,
+                    a: self.a,
+                    b: self.b,
+                    score: self.score,
+                },
+            )?;
+            foreign_key(conn.tournament_pairing.contains_key(&self.pairing))?;
+            if let Some(row) = conn.match_history.get_mut(&id) {
+                row.pairing = Some(self.pairing);
+            }
+            if let Some(row) = conn.tournament_pairing.get_mut(&self.pairing) {
+                row.score = Some(self.score);
+            }
+
+            Ok(1)
+        })
+    }
+}
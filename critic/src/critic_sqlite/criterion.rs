@@ -1,7 +1,7 @@
 use rusqlite::{params, Connection};
 
 use crate::{
-    dto::{DeleteCriterion, NewCriterion, UpdateCriterion},
+    dto::{DeleteCriterion, NewCriterion, UpdateCriterion, UpdateCriterionWeight},
     DbError, Record,
 };
 
@@ -9,23 +9,28 @@ use super::procedures;
 
 impl Record<Connection> for UpdateCriterion {
     fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
-        let mut stmt = connection
-            .prepare(procedures::UPDATE_CRITERION)
-            .expect("Failed to prepare statement");
+        let mut stmt = connection.prepare(procedures::UPDATE_CRITERION)?;
 
         stmt.execute(params![self.id, self.name])
-            .map_err(DbError::Sqlite)
+            .map_err(DbError::from)
+    }
+}
+
+impl Record<Connection> for UpdateCriterionWeight {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        let mut stmt = connection.prepare(procedures::UPDATE_CRITERION_WEIGHT)?;
+
+        stmt.execute(params![self.id, self.weight])
+            .map_err(DbError::from)
     }
 }
 
 impl Record<Connection> for NewCriterion {
     fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
-        let mut stmt = connection
-            .prepare(procedures::CREATE_CRITERION)
-            .expect("Failed to prepare statement");
+        let mut stmt = connection.prepare(procedures::CREATE_CRITERION)?;
 
         stmt.execute(params![self.group, self.name])
-            .map_err(DbError::Sqlite)
+            .map_err(DbError::from)
     }
 }
 
@@ -33,6 +38,6 @@ impl Record<Connection> for DeleteCriterion {
     fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
         connection
             .execute(procedures::DELETE_CRITERION, params![self.id])
-            .map_err(DbError::Sqlite)
+            .map_err(DbError::from)
     }
 }
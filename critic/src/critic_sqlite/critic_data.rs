@@ -1,16 +1,16 @@
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
 
-use crate::{dto, CriticData, DbError};
+use crate::{dto, elo, head_to_head, replay, CriticData, DbError};
 
-use super::procedures;
+use super::{procedures, settings};
 
 impl CriticData for Connection {
     fn next_contest(&self) -> Result<dto::Contest, DbError> {
-        let mut stmt = self
-            .prepare(procedures::NEXT_CONTEST)
-            .expect("Failed to prepare statement");
+        let cooldown = self.skip_cooldown()?;
+        let baseline = self.elo_config()?.baseline;
+        let mut stmt = self.prepare(procedures::NEXT_CONTEST)?;
 
-        stmt.query_row(params![], |r| {
+        stmt.query_row(params![cooldown, baseline], |r| {
             let a_id: i32 = r.get(0)?;
             let a_name: String = r.get(1)?;
             let a_elo: f32 = r.get(2)?;
@@ -23,16 +23,21 @@ impl CriticData for Connection {
             let cat_id: i32 = r.get(7)?;
             let cat_name: String = r.get(8)?;
             let cat_group_name: String = r.get(9)?;
+
+            let a_criterion_elo: f32 = r.get(10)?;
+            let b_criterion_elo: f32 = r.get(11)?;
             Ok(dto::Contest {
                 a: dto::Contestant {
                     id: a_id,
                     name: a_name,
                     elo: a_elo,
+                    criterion_elo: a_criterion_elo,
                 },
                 b: dto::Contestant {
                     id: b_id,
                     name: b_name,
                     elo: b_elo,
+                    criterion_elo: b_criterion_elo,
                 },
                 criterion: dto::Criterion {
                     group: cat_group_id,
@@ -42,7 +47,7 @@ impl CriticData for Connection {
                 },
             })
         })
-        .map_err(DbError::Sqlite)
+        .map_err(DbError::from)
     }
 
     fn top(
@@ -51,9 +56,7 @@ impl CriticData for Connection {
         count: usize,
         page: usize,
     ) -> Result<Vec<dto::TopRow>, DbError> {
-        let mut stmt = self
-            .prepare(procedures::TOP_CRITERIA)
-            .expect("Failed to prepare statement");
+        let mut stmt = self.prepare(procedures::TOP_CRITERIA)?;
 
         let first = page * count;
 
@@ -63,133 +66,470 @@ impl CriticData for Connection {
             params![criteria_group, count, first]
         };
 
-        let row_iter = stmt
-            .query_map(params, |r| {
-                let elo: f32 = r.get(2)?;
-                let elo = elo as i32;
-                Ok(dto::TopRow {
-                    group: r.get(0)?,
-                    entry: r.get(1)?,
-                    elo,
-                })
+        let row_iter = stmt.query_map(params, |r| {
+            let elo: f32 = r.get(2)?;
+            let elo = elo as i32;
+            let deviation: f32 = r.get(3)?;
+            let deviation = deviation as i32;
+            Ok(dto::TopRow {
+                group: r.get(0)?,
+                entry: r.get(1)?,
+                elo,
+                deviation,
+                matches: r.get(4)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in row_iter {
+            results.push(row?);
+        }
+
+        Ok(results)
+    }
+
+    fn top_criterion(
+        &self,
+        criterion: i32,
+        count: usize,
+        page: usize,
+    ) -> Result<Vec<dto::TopRow>, DbError> {
+        let mut stmt = self.prepare(procedures::TOP_CRITERION)?;
+
+        let first = page * count;
+        let initial = self.elo_config()?.initial_rating();
+
+        let params = params![criterion, count, first, initial.elo, initial.deviation];
+
+        let row_iter = stmt.query_map(params, |r| {
+            let elo: f32 = r.get(2)?;
+            let elo = elo as i32;
+            let deviation: f32 = r.get(3)?;
+            let deviation = deviation as i32;
+            Ok(dto::TopRow {
+                group: r.get(0)?,
+                entry: r.get(1)?,
+                elo,
+                deviation,
+                matches: r.get(4)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in row_iter {
+            results.push(row?);
+        }
+
+        Ok(results)
+    }
+
+    fn top_composite(
+        &self,
+        criteria_group: &str,
+        count: usize,
+        page: usize,
+    ) -> Result<Vec<dto::TopRow>, DbError> {
+        let mut stmt = self.prepare(procedures::TOP_COMPOSITE)?;
+
+        let first = page * count;
+        let initial = self.elo_config()?.initial_rating();
+        let (baseline, deviation) = (initial.elo, initial.deviation);
+
+        let params = if criteria_group.is_empty() {
+            params![rusqlite::types::Null, count, first, baseline, deviation]
+        } else {
+            params![criteria_group, count, first, baseline, deviation]
+        };
+
+        let row_iter = stmt.query_map(params, |r| {
+            let elo: f32 = r.get(2)?;
+            let elo = elo as i32;
+            let deviation: f32 = r.get(3)?;
+            let deviation = deviation as i32;
+            Ok(dto::TopRow {
+                group: r.get(0)?,
+                entry: r.get(1)?,
+                elo,
+                deviation,
+                matches: r.get(4)?,
             })
-            .map_err(DbError::Sqlite)?;
+        })?;
 
         let mut results = Vec::new();
         for row in row_iter {
-            results.push(row.unwrap());
+            results.push(row?);
         }
 
         Ok(results)
     }
 
     fn all_groups(&self) -> Result<Vec<dto::CriteriaGroup>, DbError> {
-        let mut stmt = self
-            .prepare(procedures::ALL_GROUPS)
-            .expect("Failed to prepare statement");
+        let mut stmt = self.prepare(procedures::ALL_GROUPS)?;
 
-        let row_iter = stmt
-            .query_map(params![], |r| {
-                Ok(dto::CriteriaGroup {
-                    id: r.get(0)?,
-                    name: r.get(1)?,
-                })
+        let row_iter = stmt.query_map(params![], |r| {
+            Ok(dto::CriteriaGroup {
+                id: r.get(0)?,
+                name: r.get(1)?,
             })
-            .map_err(DbError::Sqlite)?;
+        })?;
 
         let mut results = Vec::new();
         for row in row_iter {
-            results.push(row.unwrap());
+            results.push(row?);
         }
 
         Ok(results)
     }
 
     fn criteria(&self, id: i32) -> Result<Vec<dto::CriteriaGroupItem>, DbError> {
-        let mut stmt = self
-            .prepare(procedures::FIND_CRITERIA)
-            .expect("Failed to prepare statement");
+        let mut stmt = self.prepare(procedures::FIND_CRITERIA)?;
 
-        let row_iter = stmt
-            .query_map(params![id], |r| {
-                Ok(dto::CriteriaGroupItem {
-                    id: r.get(0)?,
-                    name: r.get(1)?,
-                })
+        let row_iter = stmt.query_map(params![id], |r| {
+            Ok(dto::CriteriaGroupItem {
+                id: r.get(0)?,
+                name: r.get(1)?,
+                weight: r.get(2)?,
             })
-            .map_err(DbError::Sqlite)?;
+        })?;
 
         let mut results = Vec::new();
         for row in row_iter {
-            results.push(row.unwrap());
+            results.push(row?);
         }
 
         Ok(results)
     }
 
     fn all_titles(&self) -> Result<Vec<dto::Title>, DbError> {
-        let mut stmt = self
-            .prepare(procedures::ALL_TITLES)
-            .expect("Failed to prepare statement");
+        let mut stmt = self.prepare(procedures::ALL_TITLES)?;
 
-        let row_iter = stmt
-            .query_map(params![], |r| {
-                Ok(dto::Title {
-                    id: r.get(0)?,
-                    name: r.get(1)?,
-                })
+        let row_iter = stmt.query_map(params![], |r| {
+            Ok(dto::Title {
+                id: r.get(0)?,
+                name: r.get(1)?,
             })
-            .map_err(DbError::Sqlite)?;
+        })?;
 
         let mut results = Vec::new();
         for row in row_iter {
-            results.push(row.unwrap());
+            results.push(row?);
         }
 
         Ok(results)
     }
 
+    fn title_details(&self, title: i32) -> Result<dto::TitleDetails, DbError> {
+        let mut details = self.query_row(procedures::FIND_TITLE_DETAILS, params![title], |r| {
+            Ok(dto::TitleDetails {
+                id: r.get(0)?,
+                name: r.get(1)?,
+                year: r.get(2)?,
+                platform: r.get(3)?,
+                creator: r.get(4)?,
+                notes: r.get(5)?,
+                fields: Vec::new(),
+            })
+        })?;
+
+        let mut stmt = self.prepare(procedures::FIND_TITLE_FIELDS)?;
+        let row_iter = stmt.query_map(params![title], |r| {
+            Ok(dto::TitleField {
+                key: r.get(0)?,
+                value: r.get(1)?,
+            })
+        })?;
+        for row in row_iter {
+            details.fields.push(row?);
+        }
+
+        Ok(details)
+    }
+
     fn groups_by_title(&self, id: i32) -> Result<Vec<dto::CriteriaGroup>, DbError> {
-        let mut stmt = self
-            .prepare(procedures::FIND_GROUPS_BY_TITLE)
-            .expect("Failed to prepare statement");
+        let mut stmt = self.prepare(procedures::FIND_GROUPS_BY_TITLE)?;
 
-        let row_iter = stmt
-            .query_map(params![id], |r| {
-                Ok(dto::CriteriaGroup {
-                    id: r.get(0)?,
-                    name: r.get(1)?,
-                })
+        let row_iter = stmt.query_map(params![id], |r| {
+            Ok(dto::CriteriaGroup {
+                id: r.get(0)?,
+                name: r.get(1)?,
             })
-            .map_err(DbError::Sqlite)?;
+        })?;
 
         let mut results = Vec::new();
         for row in row_iter {
-            results.push(row.unwrap());
+            results.push(row?);
         }
 
         Ok(results)
     }
 
     fn titles_in_group(&self, id: i32) -> Result<Vec<dto::Title>, DbError> {
-        let mut stmt = self
-            .prepare(procedures::FIND_TITLES_BY_GROUP)
-            .expect("Failed to prepare statement");
+        let mut stmt = self.prepare(procedures::FIND_TITLES_BY_GROUP)?;
+
+        let row_iter = stmt.query_map(params![id], |r| {
+            Ok(dto::Title {
+                id: r.get(0)?,
+                name: r.get(1)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in row_iter {
+            results.push(row?);
+        }
+
+        Ok(results)
+    }
+
+    fn rating_system(&self) -> Result<dto::RatingSystem, DbError> {
+        let system = settings::find_setting(self, settings::RATING_SYSTEM)?;
+        Ok(system.and_then(|x| x.parse().ok()).unwrap_or_default())
+    }
+
+    fn elo_config(&self) -> Result<elo::EloConfig, DbError> {
+        let default = elo::EloConfig::default();
+        let number = |key: &str, default: f32| -> Result<f32, DbError> {
+            let value = settings::find_setting(self, key)?;
+            Ok(value.and_then(|x| x.parse().ok()).unwrap_or(default))
+        };
+
+        Ok(elo::EloConfig {
+            k_schedule: settings::find_setting(self, settings::ELO_K_SCHEDULE)?
+                .and_then(|x| elo::parse_k_schedule(&x).ok())
+                .unwrap_or(default.k_schedule),
+            k_default: number(settings::ELO_K_DEFAULT, default.k_default)?,
+            provisional_matches: settings::find_setting(self, settings::ELO_PROVISIONAL_MATCHES)?
+                .and_then(|x| x.parse().ok())
+                .unwrap_or(default.provisional_matches),
+            provisional_k: number(settings::ELO_PROVISIONAL_K, default.provisional_k)?,
+            floor: number(settings::ELO_FLOOR, default.floor)?,
+            baseline: number(settings::ELO_BASELINE, default.baseline)?,
+        })
+    }
+
+    fn skip_cooldown(&self) -> Result<u32, DbError> {
+        let hours = settings::find_setting(self, settings::SKIP_COOLDOWN)?;
+        Ok(hours
+            .and_then(|x| x.parse().ok())
+            .unwrap_or(dto::DEFAULT_SKIP_COOLDOWN))
+    }
+
+    fn contest_strategy(&self) -> Result<dto::ContestStrategy, DbError> {
+        let strategy = settings::find_setting(self, settings::CONTEST_STRATEGY)?;
+        Ok(strategy.and_then(|x| x.parse().ok()).unwrap_or_default())
+    }
+
+    fn score_scale(&self) -> Result<dto::ScoreScale, DbError> {
+        let scale = settings::find_setting(self, settings::SCORE_SCALE)?;
+        Ok(scale.and_then(|x| x.parse().ok()).unwrap_or_default())
+    }
 
-        let row_iter = stmt
-            .query_map(params![id], |r| {
-                Ok(dto::Title {
-                    id: r.get(0)?,
-                    name: r.get(1)?,
-                })
+    fn skips(&self) -> Result<Vec<dto::Skip>, DbError> {
+        let cooldown = self.skip_cooldown()?;
+        let mut stmt = self.prepare(procedures::RECENT_SKIPS)?;
+
+        let row_iter = stmt.query_map(params![cooldown], |r| {
+            Ok(dto::Skip {
+                criterion: r.get(0)?,
+                a: r.get(1)?,
+                b: r.get(2)?,
+                reason: r.get(3)?,
+                time: r.get(4)?,
             })
-            .map_err(DbError::Sqlite)?;
+        })?;
 
         let mut results = Vec::new();
         for row in row_iter {
-            results.push(row.unwrap());
+            results.push(row?);
         }
 
         Ok(results)
     }
+
+    fn matches(&self) -> Result<Vec<dto::MatchRecord>, DbError> {
+        let mut stmt = self.prepare(procedures::ALL_MATCHES)?;
+
+        let row_iter = stmt.query_map(params![], |r| {
+            Ok(dto::MatchRecord {
+                id: r.get(0)?,
+                criteria_group: r.get(1)?,
+                criterion: r.get(2)?,
+                a: r.get(3)?,
+                b: r.get(4)?,
+                score: r.get(5)?,
+                elo_adj_a: r.get(6)?,
+                elo_adj_b: r.get(7)?,
+                criterion_adj_a: r.get(8)?,
+                criterion_adj_b: r.get(9)?,
+                time: r.get(10)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in row_iter {
+            results.push(row?);
+        }
+
+        Ok(results)
+    }
+
+    fn match_history(
+        &self,
+        title: Option<i32>,
+        criterion: Option<i32>,
+        count: usize,
+        page: usize,
+    ) -> Result<Vec<dto::MatchRecord>, DbError> {
+        let mut stmt = self.prepare(procedures::MATCH_HISTORY)?;
+
+        let row_iter = stmt.query_map(params![title, criterion, count, page * count], |r| {
+            Ok(dto::MatchRecord {
+                id: r.get(0)?,
+                criteria_group: r.get(1)?,
+                criterion: r.get(2)?,
+                a: r.get(3)?,
+                b: r.get(4)?,
+                score: r.get(5)?,
+                elo_adj_a: r.get(6)?,
+                elo_adj_b: r.get(7)?,
+                criterion_adj_a: r.get(8)?,
+                criterion_adj_b: r.get(9)?,
+                time: r.get(10)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in row_iter {
+            results.push(row?);
+        }
+
+        Ok(results)
+    }
+
+    fn ratings(&self) -> Result<Vec<dto::TitleRating>, DbError> {
+        let mut stmt = self.prepare(procedures::ALL_RATINGS)?;
+
+        let row_iter = stmt.query_map(params![], |r| {
+            Ok(dto::TitleRating {
+                title: r.get(0)?,
+                group: r.get(1)?,
+                criterion: r.get(2)?,
+                rating: dto::Rating {
+                    elo: r.get(3)?,
+                    deviation: r.get(4)?,
+                    volatility: r.get(5)?,
+                    matches: r.get(6)?,
+                },
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in row_iter {
+            results.push(row?);
+        }
+
+        Ok(results)
+    }
+
+    fn rating_history(&self, title: i32) -> Result<Vec<dto::RatingHistory>, DbError> {
+        Ok(replay::history(title, &self.ratings()?, &self.matches()?))
+    }
+
+    fn head_to_head(&self, a: i32, b: i32) -> Result<Vec<dto::HeadToHead>, DbError> {
+        head_to_head::compare(self, a, b)
+    }
+
+    fn exclusions(&self) -> Result<Vec<dto::Exclusion>, DbError> {
+        let mut stmt = self.prepare(procedures::ALL_EXCLUSIONS)?;
+
+        let row_iter = stmt.query_map(params![], |r| {
+            Ok(dto::Exclusion {
+                title: r.get(0)?,
+                criterion: r.get(1)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in row_iter {
+            results.push(row?);
+        }
+
+        Ok(results)
+    }
+
+    fn active_tournament(&self) -> Result<Option<dto::Tournament>, DbError> {
+        let mut stmt = self.prepare(procedures::ACTIVE_TOURNAMENT)?;
+
+        stmt.query_row(params![], |r| {
+            Ok(dto::Tournament {
+                id: r.get(0)?,
+                group: r.get(1)?,
+                criterion: r.get(2)?,
+                rounds: r.get(3)?,
+                round: r.get(4)?,
+            })
+        })
+        .optional()
+        .map_err(DbError::from)
+    }
+
+    fn tournament_pairings(&self, tournament: i32) -> Result<Vec<dto::TournamentPairing>, DbError> {
+        let mut stmt = self.prepare(procedures::TOURNAMENT_PAIRINGS)?;
+
+        let row_iter = stmt.query_map(params![tournament], |r| {
+            Ok(dto::TournamentPairing {
+                id: r.get(0)?,
+                tournament: r.get(1)?,
+                round: r.get(2)?,
+                a: r.get(3)?,
+                b: r.get(4)?,
+                score: r.get(5)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in row_iter {
+            results.push(row?);
+        }
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{critic_sqlite::test_connection, dto::SetEloConfig, DbConnection};
+
+    #[test]
+    fn unrated_criteria_start_at_baseline() {
+        let mut conn = test_connection();
+        conn.execute_batch(
+            "INSERT INTO titles (id, name) VALUES (3, 'C');
+            INSERT INTO title_criteria (title_id, group_id) VALUES (3, 1);
+            INSERT INTO title_criterion (title_id, criterion_id, elo) VALUES (1, 1, 1500.0), (2, 1, 1000.0);",
+        )
+        .unwrap();
+        let mut config = conn.elo_config().unwrap();
+        config.baseline = 1500.0;
+        conn.save(&SetEloConfig { config }).unwrap();
+
+        // C is unrated, so it starts level with A rather than B.
+        let contest = conn.next_contest().unwrap();
+        assert_eq!((contest.a.id, contest.b.id), (1, 3));
+        assert_eq!(contest.b.criterion_elo, 1500.0);
+    }
+
+    #[test]
+    fn unrated_criteria_report_initial_rating() {
+        let conn = test_connection();
+        let initial = conn.elo_config().unwrap().initial_rating();
+
+        let rows = conn.top_criterion(1, 10, 0).unwrap();
+        assert_eq!(rows.len(), 2);
+        for row in rows.iter().chain(&conn.top_composite("", 10, 0).unwrap()) {
+            assert_eq!(row.elo, initial.elo as i32);
+            assert_eq!(row.deviation, initial.deviation as i32);
+        }
+    }
 }
@@ -0,0 +1,81 @@
+use rusqlite::{params, Connection};
+
+use crate::{
+    dto::{ExcludeTitle, IncludeTitle},
+    DbError, Record,
+};
+
+use super::procedures;
+
+impl Record<Connection> for ExcludeTitle {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        connection
+            .execute(
+                procedures::ADD_EXCLUSION,
+                params![self.title, self.criterion],
+            )
+            .map_err(DbError::from)
+    }
+}
+
+impl Record<Connection> for IncludeTitle {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        connection
+            .execute(
+                procedures::DELETE_EXCLUSION,
+                params![self.title, self.criterion],
+            )
+            .map_err(DbError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{critic_sqlite::test_connection, dto::Exclusion, CriticData, DbConnection};
+
+    #[test]
+    fn excluded_titles_are_not_contested() {
+        let mut conn = test_connection();
+        let global = ExcludeTitle {
+            title: 1,
+            criterion: None,
+        };
+        let criterion = ExcludeTitle {
+            title: 2,
+            criterion: Some(1),
+        };
+
+        conn.save(&global).unwrap();
+        assert_eq!(conn.save(&global).unwrap(), 0);
+        conn.save(&criterion).unwrap();
+        assert_eq!(
+            conn.exclusions().unwrap(),
+            vec![
+                Exclusion {
+                    title: 1,
+                    criterion: None
+                },
+                Exclusion {
+                    title: 2,
+                    criterion: Some(1)
+                },
+            ]
+        );
+        assert!(conn.next_contest().is_err());
+        assert_eq!(conn.top("Group", 10, 0).unwrap().len(), 2);
+
+        conn.save(&IncludeTitle {
+            title: 1,
+            criterion: None,
+        })
+        .unwrap();
+        conn.save(&IncludeTitle {
+            title: 2,
+            criterion: Some(1),
+        })
+        .unwrap();
+        assert!(conn.exclusions().unwrap().is_empty());
+        assert!(conn.next_contest().is_ok());
+    }
+}
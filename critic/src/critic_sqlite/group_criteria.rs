@@ -2,29 +2,25 @@ use rusqlite::{params, Connection};
 
 use crate::{
     dto::{DeleteCriteriaGroup, GroupAddToTiles, NewCriteriaGroup, UpdateCriteriaGroup},
-    DbError, Record,
+    CriticData, DbError, Record,
 };
 
 use super::procedures;
 
 impl Record<Connection> for UpdateCriteriaGroup {
     fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
-        let mut stmt = connection
-            .prepare(procedures::UPDATE_GROUP_CRITERIA)
-            .expect("Failed to prepare statement");
+        let mut stmt = connection.prepare(procedures::UPDATE_GROUP_CRITERIA)?;
 
         stmt.execute(params![self.id, self.name])
-            .map_err(DbError::Sqlite)
+            .map_err(DbError::from)
     }
 }
 
 impl Record<Connection> for NewCriteriaGroup {
     fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
-        let mut stmt = connection
-            .prepare(procedures::CREATE_GROUP_CRITERIA)
-            .expect("Failed to prepare statement");
+        let mut stmt = connection.prepare(procedures::CREATE_GROUP_CRITERIA)?;
 
-        stmt.execute(params![self.name]).map_err(DbError::Sqlite)?;
+        stmt.execute(params![self.name])?;
 
         Ok(connection.last_insert_rowid() as usize)
     }
@@ -34,14 +30,15 @@ impl Record<Connection> for DeleteCriteriaGroup {
     fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
         connection
             .execute(procedures::DELETE_GROUP_CRITERIA, params![self.id])
-            .map_err(DbError::Sqlite)
+            .map_err(DbError::from)
     }
 }
 
 impl Record<Connection> for GroupAddToTiles {
     fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        let baseline = connection.elo_config()?.baseline;
         connection
-            .execute(procedures::ADD_GROUP_TO_ALL, params![self.id])
-            .map_err(DbError::Sqlite)
+            .execute(procedures::ADD_GROUP_TO_ALL, params![self.id, baseline])
+            .map_err(DbError::from)
     }
 }
@@ -1,44 +1,477 @@
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
 
-use crate::{dto::MatchResult, DbError, Record};
+use crate::{
+    dto::{DeleteMatch, FlipMatch, MatchResult, Rating, RatingSystem, Rejudge, UndoLastMatch},
+    replay, CriticData, DbError, Record,
+};
 
-use super::procedures;
+use super::{procedures, rating};
+
+fn find_rating(
+    tx: &Transaction,
+    sql: &str,
+    title: i32,
+    id: i32,
+    initial: Rating,
+) -> Result<Rating, DbError> {
+    let mut stmt = tx.prepare(sql)?;
+
+    stmt.query_row(params![title, id], |r| {
+        Ok(Rating {
+            elo: r.get(0)?,
+            deviation: r.get(1)?,
+            volatility: r.get(2)?,
+            matches: r.get(3)?,
+        })
+    })
+    .optional()
+    .map(|x| x.unwrap_or(initial))
+    .map_err(DbError::from)
+}
 
 impl Record<Connection> for MatchResult {
     fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
-        let tx = connection
-            .transaction()
-            .expect("Save transaction could not be started");
+        let tx = connection.transaction()?;
+        add_match(&tx, self)?;
+
+        tx.commit().map_err(DbError::from).map(|_| 1)
+    }
+}
+
+/// Records `result` and moves both titles' ratings as part of a larger
+/// transaction, returning the match id.
+pub(super) fn add_match(tx: &Transaction, result: &MatchResult) -> Result<i64, DbError> {
+    let config = tx.elo_config()?;
+    let model = tx.rating_system()?.model(&config);
+    let initial = config.initial_rating();
+
+    let group = (
+        find_rating(
+            tx,
+            procedures::FIND_RATING,
+            result.a,
+            result.criteria_group,
+            initial,
+        )?,
+        find_rating(
+            tx,
+            procedures::FIND_RATING,
+            result.b,
+            result.criteria_group,
+            initial,
+        )?,
+    );
+    let criterion = (
+        find_rating(
+            tx,
+            procedures::FIND_CRITERION_RATING,
+            result.a,
+            result.criterion,
+            initial,
+        )?,
+        find_rating(
+            tx,
+            procedures::FIND_CRITERION_RATING,
+            result.b,
+            result.criterion,
+            initial,
+        )?,
+    );
+
+    let new_group = model.calc_change(group.0, group.1, result.score);
+    let new_group = (new_group.0.played(), new_group.1.played());
+    let new_criterion = model.calc_change(criterion.0, criterion.1, result.score);
+    let new_criterion = (new_criterion.0.played(), new_criterion.1.played());
+
+    let mut ins_stmt = tx.prepare(procedures::ADD_CONTEST_RESULT)?;
+
+    let mut update_stmt = tx.prepare(procedures::UPDATE_RATING)?;
+
+    let mut update_criterion_stmt = tx.prepare(procedures::UPDATE_CRITERION_RATING)?;
+
+    ins_stmt.execute(params![
+        result.criterion,
+        result.a,
+        result.b,
+        result.score,
+        new_group.0.elo - group.0.elo,
+        new_group.1.elo - group.1.elo,
+        new_criterion.0.elo - criterion.0.elo,
+        new_criterion.1.elo - criterion.1.elo,
+    ])?;
+    let id = tx.last_insert_rowid();
+
+    for (id, rating) in [(result.a, new_group.0), (result.b, new_group.1)] {
+        update_stmt.execute(params![
+            id,
+            result.criteria_group,
+            rating.elo,
+            rating.deviation,
+            rating.volatility,
+            rating.matches,
+        ])?;
+    }
+
+    for (id, rating) in [(result.a, new_criterion.0), (result.b, new_criterion.1)] {
+        update_criterion_stmt.execute(params![
+            id,
+            result.criterion,
+            rating.elo,
+            rating.deviation,
+            rating.volatility,
+            rating.matches,
+        ])?;
+    }
+
+    Ok(id)
+}
+
+impl Record<Connection> for UndoLastMatch {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        let tx = connection.transaction()?;
 
         {
-            let mut ins_stmt = tx
-                .prepare(procedures::ADD_CONTEST_RESULT)
-                .expect("Failed to prepare statement");
-
-            let mut update_stmt = tx
-                .prepare(procedures::UPDATE_ELO)
-                .expect("Failed to prepare statement");
-
-            ins_stmt
-                .execute(params![
-                    self.criterion,
-                    self.a,
-                    self.b,
-                    self.score,
-                    self.elo_change.0,
-                    self.elo_change.1,
-                ])
-                .map_err(DbError::Sqlite)?;
-
-            update_stmt
-                .execute(params![self.a, self.criteria_group, self.elo_change.0])
-                .map_err(DbError::Sqlite)?;
-
-            update_stmt
-                .execute(params![self.b, self.criteria_group, self.elo_change.1])
-                .map_err(DbError::Sqlite)?;
-        }
-
-        tx.commit().map_err(DbError::Sqlite).map(|_| 1)
+            let system = tx.rating_system()?;
+            let mut stmt = tx.prepare(procedures::LAST_MATCH)?;
+
+            let last = stmt
+                .query_row(params![], |r| {
+                    Ok((
+                        r.get::<_, i32>(0)?,
+                        r.get::<_, i32>(1)?,
+                        r.get::<_, i32>(2)?,
+                        [
+                            (
+                                r.get::<_, i32>(3)?,
+                                r.get::<_, f32>(5)?,
+                                r.get::<_, f32>(7)?,
+                            ),
+                            (
+                                r.get::<_, i32>(4)?,
+                                r.get::<_, f32>(6)?,
+                                r.get::<_, f32>(8)?,
+                            ),
+                        ],
+                    ))
+                })
+                .optional()?;
+
+            let Some((id, group, criterion, contestants)) = last else {
+                return Ok(0);
+            };
+
+            tx.execute(procedures::UNSCORE_PAIRING, params![id])?;
+            tx.execute(procedures::DELETE_MATCH, params![id])?;
+            if system == RatingSystem::Elo {
+                let mut revert_stmt = tx.prepare(procedures::REVERT_RATING)?;
+
+                let mut revert_criterion_stmt = tx.prepare(procedures::REVERT_CRITERION_RATING)?;
+
+                for (title, adj, criterion_adj) in contestants {
+                    revert_stmt.execute(params![title, group, adj])?;
+                    revert_criterion_stmt.execute(params![title, criterion, criterion_adj])?;
+                }
+            } else {
+                recompute(&tx)?;
+            }
+        }
+
+        tx.commit().map_err(DbError::from).map(|_| 1)
+    }
+}
+
+/// Recomputes every rating as part of a larger transaction.
+fn recompute(tx: &Transaction) -> Result<(), DbError> {
+    let (update, _) = replay::rebuild(&**tx)?;
+    rating::update_ratings(tx, &update)
+}
+
+impl Record<Connection> for DeleteMatch {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        let tx = connection.transaction()?;
+
+        let deleted = tx.execute(procedures::DELETE_MATCH, params![self.id])?;
+        if deleted > 0 {
+            recompute(&tx)?;
+        }
+
+        tx.commit().map_err(DbError::from).map(|_| deleted)
+    }
+}
+
+impl Record<Connection> for FlipMatch {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        let tx = connection.transaction()?;
+
+        let flipped = tx.execute(procedures::FLIP_MATCH, params![self.id])?;
+        if flipped > 0 {
+            recompute(&tx)?;
+        }
+
+        tx.commit().map_err(DbError::from).map(|_| flipped)
+    }
+}
+
+impl Record<Connection> for Rejudge {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        let tx = connection.transaction()?;
+
+        let deleted = tx.execute(
+            procedures::DELETE_PAIR_MATCHES,
+            params![self.criterion, self.a, self.b],
+        )?;
+        recompute(&tx)?;
+        add_match(
+            &tx,
+            &MatchResult {
+                criteria_group: self.criteria_group,
+                criterion: self.criterion,
+                a: self.a,
+                b: self.b,
+                score: self.score,
+            },
+        )?;
+
+        tx.commit().map_err(DbError::from).map(|_| deleted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        critic_sqlite::test_connection,
+        dto::{MatchRecord, SetEloConfig, SetRatingSystem},
+        elo::EloConfig,
+        DbConnection,
+    };
+
+    fn result(score: f32) -> MatchResult {
+        MatchResult {
+            criteria_group: 1,
+            criterion: 1,
+            a: 1,
+            b: 2,
+            score,
+        }
+    }
+
+    #[test]
+    fn undo_restores_ratings() {
+        for system in [RatingSystem::Elo, RatingSystem::Glicko2] {
+            let mut conn = test_connection();
+            conn.save(&SetRatingSystem { system }).unwrap();
+            conn.save(&result(0.0)).unwrap();
+            let before = conn.ratings().unwrap();
+
+            conn.save(&result(1.0)).unwrap();
+            assert_eq!(conn.save(&UndoLastMatch).unwrap(), 1);
+
+            let matches: Vec<MatchRecord> = conn.matches().unwrap();
+            assert_eq!(matches.len(), 1);
+            assert_eq!(matches[0].score, 0.0);
+            for (a, b) in before.iter().zip(conn.ratings().unwrap()) {
+                assert_eq!(a.title, b.title);
+                assert!((a.rating.elo - b.rating.elo).abs() < 0.01);
+                assert!((a.rating.deviation - b.rating.deviation).abs() < 0.01);
+                assert!((a.rating.volatility - b.rating.volatility).abs() < 1e-6);
+                assert_eq!(a.rating.matches, b.rating.matches);
+            }
+
+            assert_eq!(conn.save(&UndoLastMatch).unwrap(), 1);
+            assert_eq!(conn.save(&UndoLastMatch).unwrap(), 0);
+        }
+    }
+
+    #[test]
+    fn uses_stored_elo_config() {
+        let mut conn = test_connection();
+        conn.save(&SetEloConfig {
+            config: EloConfig {
+                k_schedule: vec![(1400.0, 10.0)],
+                k_default: 20.0,
+                provisional_matches: 0,
+                provisional_k: 0.0,
+                floor: 0.0,
+                baseline: 1500.0,
+            },
+        })
+        .unwrap();
+        conn.save(&result(1.0)).unwrap();
+
+        let elo = |criterion: Option<i32>| -> Vec<f32> {
+            conn.ratings()
+                .unwrap()
+                .into_iter()
+                .filter(|x| x.criterion == criterion)
+                .map(|x| x.rating.elo)
+                .collect()
+        };
+        // Group ratings were created before the baseline changed.
+        assert_eq!(elo(None), vec![1010.0, 990.0]);
+        assert_eq!(elo(Some(1)), vec![1505.0, 1495.0]);
+    }
+
+    #[test]
+    fn counts_matches_until_undone() {
+        let mut conn = test_connection();
+        conn.save(&result(1.0)).unwrap();
+        conn.save(&result(0.5)).unwrap();
+
+        let matches = |conn: &Connection| -> Vec<i32> {
+            conn.ratings()
+                .unwrap()
+                .into_iter()
+                .map(|x| x.rating.matches)
+                .collect()
+        };
+        assert_eq!(matches(&conn), vec![2, 2, 2, 2]);
+
+        conn.save(&UndoLastMatch).unwrap();
+        assert_eq!(matches(&conn), vec![1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn rejudging_breaks_a_cycle() {
+        let mut conn = test_connection();
+        conn.execute_batch(
+            "INSERT INTO titles (id, name) VALUES (3, 'C');
+            INSERT INTO title_criteria (title_id, group_id) VALUES (3, 1);",
+        )
+        .unwrap();
+        for (a, b) in [(1, 2), (2, 3), (3, 1)] {
+            conn.save(&MatchResult {
+                criteria_group: 1,
+                criterion: 1,
+                a,
+                b,
+                score: 1.0,
+            })
+            .unwrap();
+        }
+
+        let conflicts = crate::cycles::conflicts(&conn).unwrap();
+        assert_eq!(conflicts.len(), 1);
+        let judgment = &conflicts[0].preference;
+
+        let deleted = conn
+            .save(&Rejudge {
+                criteria_group: 1,
+                criterion: 1,
+                a: judgment.winner,
+                b: judgment.loser,
+                score: 0.0,
+            })
+            .unwrap();
+        assert_eq!(deleted, 1);
+        assert!(crate::cycles::conflicts(&conn).unwrap().is_empty());
+        assert_eq!(conn.matches().unwrap().len(), 3);
+        assert!(conn
+            .ratings()
+            .unwrap()
+            .iter()
+            .all(|x| x.rating.matches == 2));
+    }
+
+    #[test]
+    fn history_ends_at_current_rating() {
+        let mut conn = test_connection();
+        for score in [1.0, 1.0, 0.0] {
+            conn.save(&result(score)).unwrap();
+        }
+
+        let history = conn.rating_history(1).unwrap();
+        assert_eq!(
+            history
+                .iter()
+                .map(|x| (x.group, x.criterion))
+                .collect::<Vec<_>>(),
+            vec![(1, None), (1, Some(1))]
+        );
+
+        let ratings = conn.ratings().unwrap();
+        for series in &history {
+            let current = ratings
+                .iter()
+                .find(|x| x.title == 1 && x.criterion == series.criterion)
+                .unwrap();
+            assert_eq!(series.start, 1000.0);
+            assert_eq!(series.points.len(), 3);
+            assert_eq!(series.points[2].elo, current.rating.elo);
+            assert!(series.points[1].elo > series.points[0].elo);
+            assert_eq!(series.stable_since(1000.0), series.points.first());
+        }
+    }
+
+    #[test]
+    fn flip_and_delete_correct_ratings() {
+        let mut conn = test_connection();
+        conn.save(&result(1.0)).unwrap();
+        conn.save(&result(1.0)).unwrap();
+
+        let page = conn.match_history(Some(2), Some(1), 1, 0).unwrap();
+        assert_eq!(page.len(), 1);
+        let newest = page[0].id;
+        assert_eq!(conn.match_history(None, None, 10, 1).unwrap().len(), 0);
+        assert!(conn.match_history(Some(3), None, 10, 0).unwrap().is_empty());
+
+        assert_eq!(conn.save(&FlipMatch { id: newest }).unwrap(), 1);
+        let elo: Vec<f32> = conn
+            .ratings()
+            .unwrap()
+            .iter()
+            .map(|x| x.rating.elo)
+            .collect();
+        assert!(elo[0] < 1040.0 && elo[1] > 960.0);
+
+        assert_eq!(conn.save(&DeleteMatch { id: newest }).unwrap(), 1);
+        assert_eq!(conn.save(&DeleteMatch { id: newest }).unwrap(), 0);
+        let ratings = conn.ratings().unwrap();
+        assert_eq!(ratings[0].rating.elo, 1040.0);
+        assert_eq!(ratings[0].rating.matches, 1);
+    }
+
+    /// Makes every later rating update fail.
+    fn fail_rating_updates(conn: &Connection) {
+        conn.execute_batch(
+            "CREATE TEMP TRIGGER fail_rating_updates BEFORE UPDATE ON title_criteria
+            BEGIN SELECT RAISE(ABORT, 'rating update failed'); END;",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn failed_recompute_keeps_history() {
+        let mut conn = test_connection();
+        conn.save(&result(1.0)).unwrap();
+        conn.save(&result(0.0)).unwrap();
+        let matches = conn.matches().unwrap();
+        let ratings = conn.ratings().unwrap();
+
+        fail_rating_updates(&conn);
+        let id = matches[0].id;
+        assert!(conn.save(&FlipMatch { id }).is_err());
+        assert!(conn.save(&DeleteMatch { id }).is_err());
+
+        assert_eq!(conn.matches().unwrap(), matches);
+        assert_eq!(conn.ratings().unwrap(), ratings);
+    }
+
+    #[test]
+    fn failed_rejudge_keeps_judgments() {
+        let mut conn = test_connection();
+        conn.save(&result(1.0)).unwrap();
+        let matches = conn.matches().unwrap();
+
+        fail_rating_updates(&conn);
+        let rejudged = conn.save(&Rejudge {
+            criteria_group: 1,
+            criterion: 1,
+            a: 1,
+            b: 2,
+            score: 0.0,
+        });
+        assert!(rejudged.is_err());
+        assert_eq!(conn.matches().unwrap(), matches);
     }
 }
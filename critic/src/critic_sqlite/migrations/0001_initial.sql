@@ -1,4 +1,3 @@
-BEGIN;
 CREATE TABLE IF NOT EXISTS titles (
     id INTEGER PRIMARY KEY NOT NULL,
     name STRING NOT NULL,
@@ -42,4 +41,3 @@ CREATE TABLE IF NOT EXISTS match_history (
     FOREIGN KEY (a_id) REFERENCES titles(id) ON DELETE CASCADE,
     FOREIGN KEY (b_id) REFERENCES titles(id) ON DELETE CASCADE
 );
-COMMIT;
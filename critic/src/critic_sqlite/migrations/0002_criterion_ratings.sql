@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS title_criterion (
+    id INTEGER PRIMARY KEY NOT NULL,
+    elo REAL DEFAULT 1000.0 NOT NULL,
+    title_id INTEGER NOT NULL,
+    criterion_id INTEGER NOT NULL,
+    FOREIGN KEY (title_id) REFERENCES titles(id) ON DELETE CASCADE
+    FOREIGN KEY (criterion_id) REFERENCES criteria(id) ON DELETE CASCADE
+    UNIQUE(title_id, criterion_id)
+);
@@ -0,0 +1 @@
+ALTER TABLE criteria ADD COLUMN weight REAL DEFAULT 1.0 NOT NULL;
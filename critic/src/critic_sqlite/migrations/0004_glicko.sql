@@ -0,0 +1,10 @@
+ALTER TABLE title_criteria ADD COLUMN deviation REAL DEFAULT 350.0 NOT NULL;
+ALTER TABLE title_criteria ADD COLUMN volatility REAL DEFAULT 0.06 NOT NULL;
+
+ALTER TABLE title_criterion ADD COLUMN deviation REAL DEFAULT 350.0 NOT NULL;
+ALTER TABLE title_criterion ADD COLUMN volatility REAL DEFAULT 0.06 NOT NULL;
+
+CREATE TABLE IF NOT EXISTS settings (
+    key STRING PRIMARY KEY NOT NULL,
+    value STRING NOT NULL
+);
@@ -0,0 +1,2 @@
+ALTER TABLE match_history ADD COLUMN criterion_adj_a REAL DEFAULT 0.0 NOT NULL;
+ALTER TABLE match_history ADD COLUMN criterion_adj_b REAL DEFAULT 0.0 NOT NULL;
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS skip_history (
+    id INTEGER PRIMARY KEY NOT NULL,
+    criterion_id INTEGER NOT NULL,
+    a_id INTEGER NOT NULL,
+    b_id INTEGER NOT NULL,
+    reason STRING,
+    time DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (criterion_id) REFERENCES criteria(id) ON DELETE CASCADE,
+    FOREIGN KEY (a_id) REFERENCES titles(id) ON DELETE CASCADE,
+    FOREIGN KEY (b_id) REFERENCES titles(id) ON DELETE CASCADE
+);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS exclusions (
+    id INTEGER PRIMARY KEY NOT NULL,
+    title_id INTEGER NOT NULL,
+    criterion_id INTEGER,
+    FOREIGN KEY (title_id) REFERENCES titles(id) ON DELETE CASCADE,
+    FOREIGN KEY (criterion_id) REFERENCES criteria(id) ON DELETE CASCADE
+);
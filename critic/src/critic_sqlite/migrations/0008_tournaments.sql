@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS tournament (
+    id INTEGER PRIMARY KEY NOT NULL,
+    criterion_id INTEGER NOT NULL,
+    rounds INTEGER NOT NULL,
+    round INTEGER DEFAULT 0 NOT NULL,
+    FOREIGN KEY (criterion_id) REFERENCES criteria(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS tournament_pairing (
+    id INTEGER PRIMARY KEY NOT NULL,
+    tournament_id INTEGER NOT NULL,
+    round INTEGER NOT NULL,
+    a_id INTEGER NOT NULL,
+    b_id INTEGER,
+    score REAL,
+    FOREIGN KEY (tournament_id) REFERENCES tournament(id) ON DELETE CASCADE,
+    FOREIGN KEY (a_id) REFERENCES titles(id) ON DELETE CASCADE,
+    FOREIGN KEY (b_id) REFERENCES titles(id) ON DELETE CASCADE
+);
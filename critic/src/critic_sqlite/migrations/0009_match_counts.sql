@@ -0,0 +1,20 @@
+ALTER TABLE title_criteria ADD COLUMN matches INTEGER DEFAULT 0 NOT NULL;
+ALTER TABLE title_criterion ADD COLUMN matches INTEGER DEFAULT 0 NOT NULL;
+
+-- Count the matches already played.
+UPDATE title_criteria
+SET matches = (
+    SELECT COUNT(*)
+    FROM match_history mh
+    JOIN criteria c ON c.id = mh.criterion_id
+    WHERE c.group_id = title_criteria.group_id
+        AND title_criteria.title_id IN (mh.a_id, mh.b_id)
+);
+
+UPDATE title_criterion
+SET matches = (
+    SELECT COUNT(*)
+    FROM match_history mh
+    WHERE mh.criterion_id = title_criterion.criterion_id
+        AND title_criterion.title_id IN (mh.a_id, mh.b_id)
+);
@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS title_details (
+    title_id INTEGER PRIMARY KEY NOT NULL,
+    year INTEGER,
+    platform TEXT,
+    creator TEXT,
+    notes TEXT,
+    FOREIGN KEY (title_id) REFERENCES titles(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS title_fields (
+    id INTEGER PRIMARY KEY NOT NULL,
+    title_id INTEGER NOT NULL,
+    key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    UNIQUE (title_id, key),
+    FOREIGN KEY (title_id) REFERENCES titles(id) ON DELETE CASCADE
+);
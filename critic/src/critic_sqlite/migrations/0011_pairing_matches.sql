@@ -0,0 +1,2 @@
+ALTER TABLE match_history ADD COLUMN pairing_id INTEGER
+    REFERENCES tournament_pairing(id) ON DELETE SET NULL;
@@ -0,0 +1,196 @@
+use rusqlite::{params, Connection};
+
+use crate::DbError;
+
+/// One schema change.
+struct Migration {
+    sql: &'static str,
+    /// A table, or a table's column, the change adds. Places databases from
+    /// before versioning, which have a version of 0.
+    marker: (&'static str, Option<&'static str>),
+}
+
+/// Schema changes in the order they were made. A database's
+/// `PRAGMA user_version` is the number of them already applied.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        sql: include_str!("0001_initial.sql"),
+        marker: ("titles", None),
+    },
+    Migration {
+        sql: include_str!("0002_criterion_ratings.sql"),
+        marker: ("title_criterion", None),
+    },
+    Migration {
+        sql: include_str!("0003_criterion_weights.sql"),
+        marker: ("criteria", Some("weight")),
+    },
+    Migration {
+        sql: include_str!("0004_glicko.sql"),
+        marker: ("settings", None),
+    },
+    Migration {
+        sql: include_str!("0005_criterion_adjustments.sql"),
+        marker: ("match_history", Some("criterion_adj_a")),
+    },
+    Migration {
+        sql: include_str!("0006_skip_history.sql"),
+        marker: ("skip_history", None),
+    },
+    Migration {
+        sql: include_str!("0007_exclusions.sql"),
+        marker: ("exclusions", None),
+    },
+    Migration {
+        sql: include_str!("0008_tournaments.sql"),
+        marker: ("tournament", None),
+    },
+    Migration {
+        sql: include_str!("0009_match_counts.sql"),
+        marker: ("title_criteria", Some("matches")),
+    },
+    Migration {
+        sql: include_str!("0010_title_metadata.sql"),
+        marker: ("title_details", None),
+    },
+    Migration {
+        sql: include_str!("0011_pairing_matches.sql"),
+        marker: ("match_history", Some("pairing_id")),
+    },
+];
+
+/// Schema version this build reads and writes.
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Brings the schema up to [`SCHEMA_VERSION`], returning the version it was
+/// at. Refuses databases written by a newer build.
+pub fn migrate(conn: &mut Connection) -> Result<u32, DbError> {
+    apply(conn, MIGRATIONS)
+}
+
+fn version(conn: &Connection) -> Result<u32, DbError> {
+    conn.pragma_query_value(None, "user_version", |r| r.get(0))
+        .map_err(DbError::from)
+}
+
+/// Whether the table, or the table's column, in `marker` exists.
+fn has(conn: &Connection, marker: (&str, Option<&str>)) -> Result<bool, DbError> {
+    conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info(?1) WHERE ?2 IS NULL OR name = ?2",
+        params![marker.0, marker.1],
+        |r| r.get(0),
+    )
+    .map_err(DbError::from)
+}
+
+/// Applies each of `migrations` past the current version in its own
+/// transaction, so a failing step leaves the schema at the one before it.
+fn apply(conn: &mut Connection, migrations: &[Migration]) -> Result<u32, DbError> {
+    let supported = migrations.len() as u32;
+    let mut found = version(conn)?;
+    if found > supported {
+        return Err(DbError::SchemaTooNew { found, supported });
+    }
+    if found == 0 {
+        for migration in migrations {
+            if !has(conn, migration.marker)? {
+                break;
+            }
+            found += 1;
+        }
+        conn.pragma_update(None, "user_version", found)?;
+    }
+
+    for (step, migration) in migrations.iter().enumerate().skip(found as usize) {
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration.sql)?;
+        tx.pragma_update(None, "user_version", step as u32 + 1)?;
+        tx.commit()?;
+    }
+
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dto::MatchResult, CriticData, DbConnection};
+
+    /// A database made before versioning, holding the seed data.
+    fn seeded() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATIONS[0].sql).unwrap();
+        conn.execute_batch(include_str!("../../../../seed/vg_seed.sql"))
+            .unwrap();
+        conn
+    }
+
+    #[test]
+    fn upgrades_unversioned_seed() {
+        let mut conn = seeded();
+        assert_eq!(migrate(&mut conn).unwrap(), 1);
+        assert_eq!(version(&conn).unwrap(), SCHEMA_VERSION);
+
+        assert_eq!(conn.all_titles().unwrap().len(), 90);
+        assert!(conn.criteria(1).unwrap().iter().all(|x| x.weight == 1.0));
+        conn.save(&MatchResult {
+            criteria_group: 1,
+            criterion: 1,
+            a: 1,
+            b: 3,
+            score: 1.0,
+        })
+        .unwrap();
+        let top = conn.top_criterion(1, 1, 0).unwrap();
+        assert_eq!(top[0].entry, "Legend of Zelda");
+        assert_eq!(top[0].matches, 1);
+
+        // Already current: nothing left to do.
+        assert_eq!(migrate(&mut conn).unwrap(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn refuses_newer_schema() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION + 1)
+            .unwrap();
+
+        assert!(matches!(
+            migrate(&mut conn),
+            Err(DbError::SchemaTooNew { found, supported })
+                if found == SCHEMA_VERSION + 1 && supported == SCHEMA_VERSION
+        ));
+    }
+
+    #[test]
+    fn places_unversioned_databases() {
+        let conn = Connection::open_in_memory().unwrap();
+        for migration in &MIGRATIONS[..5] {
+            conn.execute_batch(migration.sql).unwrap();
+        }
+
+        let mut conn = conn;
+        assert_eq!(migrate(&mut conn).unwrap(), 5);
+        assert_eq!(version(&conn).unwrap(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn failed_step_rolls_back() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        let steps = [
+            Migration {
+                sql: "CREATE TABLE a (id INTEGER);",
+                marker: ("a", None),
+            },
+            Migration {
+                sql: "CREATE TABLE b (id INTEGER); INSERT INTO missing VALUES (1);",
+                marker: ("b", None),
+            },
+        ];
+
+        assert!(apply(&mut conn, &steps).is_err());
+        assert_eq!(version(&conn).unwrap(), 1);
+        assert!(!has(&conn, ("b", None)).unwrap());
+        assert_eq!(apply(&mut conn, &steps[..1]).unwrap(), 1);
+    }
+}
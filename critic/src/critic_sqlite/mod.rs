@@ -1,11 +1,58 @@
 mod connection;
 mod criterion;
 mod critic_data;
+mod exclusion;
 mod group_criteria;
 mod match_result;
+mod migrations;
 mod new_criterion_item;
+mod rating;
+mod settings;
+mod skip;
 mod title;
+mod tournament;
 
 mod procedures;
 
+pub use migrations::SCHEMA_VERSION;
 pub use rusqlite::Connection;
+
+impl From<rusqlite::Error> for crate::DbError {
+    fn from(err: rusqlite::Error) -> Self {
+        use crate::DbError;
+        use rusqlite::{ffi::ErrorCode, Error};
+
+        match err {
+            Error::QueryReturnedNoRows => DbError::NotFound,
+            Error::SqliteFailure(e, msg) => match e.code {
+                ErrorCode::ConstraintViolation => {
+                    DbError::Constraint(msg.unwrap_or_else(|| e.to_string()))
+                }
+                ErrorCode::DatabaseCorrupt | ErrorCode::NotADatabase => {
+                    DbError::Corrupt(msg.unwrap_or_else(|| e.to_string()))
+                }
+                _ => DbError::Backend(Box::new(Error::SqliteFailure(e, msg))),
+            },
+            Error::FromSqlConversionFailure(..)
+            | Error::IntegralValueOutOfRange(..)
+            | Error::InvalidColumnType(..) => DbError::Corrupt(err.to_string()),
+            err => DbError::Backend(Box::new(err)),
+        }
+    }
+}
+
+/// In-memory database with two titles sharing a group of one criterion.
+#[cfg(test)]
+pub(crate) fn test_connection() -> Connection {
+    use crate::DbConnection;
+
+    let conn = Connection::open_category(":memory:").unwrap();
+    conn.execute_batch(
+        "INSERT INTO titles (id, name) VALUES (1, 'A'), (2, 'B');
+        INSERT INTO criteria_group (id, value) VALUES (1, 'Group');
+        INSERT INTO criteria (id, value, group_id) VALUES (1, 'Criterion', 1);
+        INSERT INTO title_criteria (title_id, group_id) VALUES (1, 1), (2, 1);",
+    )
+    .unwrap();
+    conn
+}
@@ -1,39 +1,28 @@
 use rusqlite::{params, Connection};
 
-use crate::{dto::NewCategoryItem, DbError, Record};
+use crate::{dto::NewCategoryItem, CriticData, DbError, Record};
 
 use super::procedures;
 
 impl Record<Connection> for NewCategoryItem {
     fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
-        let tx = connection
-            .transaction()
-            .expect("Save transaction could not be started");
+        let baseline = connection.elo_config()?.baseline;
+        let tx = connection.transaction()?;
 
         {
-            let mut ins_stmt = tx
-                .prepare(procedures::CREATE_TITLE)
-                .expect("Failed to prepare statement");
+            let mut ins_stmt = tx.prepare(procedures::CREATE_TITLE)?;
 
-            let mut ins_sub_stmt = tx
-                .prepare(procedures::CREATE_TITLE_CRITERIA)
-                .expect("Failed to prepare statement");
+            let mut ins_sub_stmt = tx.prepare(procedures::CREATE_TITLE_CRITERIA)?;
 
-            ins_stmt
-                .execute(params![self.name])
-                .map_err(DbError::Sqlite)?;
+            ins_stmt.execute(params![self.name])?;
 
-            ins_sub_stmt
-                .execute(params![self.name, "General"])
-                .map_err(DbError::Sqlite)?;
+            ins_sub_stmt.execute(params![self.name, "General", baseline])?;
 
             for sc in self.sub_categories.iter().filter(|x| !x.is_empty()) {
-                ins_sub_stmt
-                    .execute(params![self.name, sc])
-                    .map_err(DbError::Sqlite)?;
+                ins_sub_stmt.execute(params![self.name, sc, baseline])?;
             }
         }
 
-        tx.commit().map_err(DbError::Sqlite).map(|_| 1)
+        tx.commit().map_err(DbError::from).map(|_| 1)
     }
 }
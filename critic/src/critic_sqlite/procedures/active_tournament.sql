@@ -0,0 +1,10 @@
+SELECT t.id, c.group_id, t.criterion_id, t.rounds, t.round
+FROM tournament t
+JOIN criteria c ON c.id = t.criterion_id
+WHERE t.round < t.rounds
+    OR EXISTS (
+        SELECT 1 FROM tournament_pairing p
+        WHERE p.tournament_id = t.id AND p.b_id IS NOT NULL AND p.score IS NULL
+    )
+ORDER BY t.id DESC
+LIMIT 1
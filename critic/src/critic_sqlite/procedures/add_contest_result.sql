@@ -1,2 +1,2 @@
-INSERT INTO match_history (criterion_id, a_id, b_id, score, elo_adj_a, elo_adj_b)
-VALUES (?1, ?2, ?3, ?4, ?5, ?6);
+INSERT INTO match_history (criterion_id, a_id, b_id, score, elo_adj_a, elo_adj_b, criterion_adj_a, criterion_adj_b)
+VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);
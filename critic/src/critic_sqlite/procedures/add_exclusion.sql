@@ -0,0 +1,5 @@
+INSERT INTO exclusions (title_id, criterion_id)
+SELECT ?1, ?2
+WHERE NOT EXISTS (
+    SELECT 1 FROM exclusions WHERE title_id = ?1 AND criterion_id IS ?2
+)
@@ -1,5 +1,5 @@
-INSERT INTO title_criteria (title_id, group_id)
-SELECT t.id, ?1
+INSERT INTO title_criteria (title_id, group_id, elo)
+SELECT t.id, ?1, ?2
 FROM titles t
 WHERE NOT EXISTS (
     SELECT 1
@@ -0,0 +1,2 @@
+INSERT INTO skip_history (criterion_id, a_id, b_id, reason)
+VALUES (?1, ?2, ?3, ?4);
@@ -0,0 +1,2 @@
+SELECT title_id, criterion_id FROM exclusions
+ORDER BY title_id, criterion_id
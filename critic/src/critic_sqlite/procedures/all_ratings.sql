@@ -0,0 +1,6 @@
+SELECT title_id, group_id, NULL, elo, deviation, volatility, matches FROM title_criteria
+UNION ALL
+SELECT tcr.title_id, c.group_id, tcr.criterion_id, tcr.elo, tcr.deviation, tcr.volatility,
+    tcr.matches
+FROM title_criterion tcr
+JOIN criteria c ON c.id = tcr.criterion_id
@@ -0,0 +1,2 @@
+INSERT INTO tournament_pairing (tournament_id, round, a_id, b_id)
+VALUES (?1, ?2, ?3, ?4)
@@ -1,2 +1,2 @@
-INSERT INTO title_criteria (title_id, group_id)
-VALUES (?1, ?2)
+INSERT INTO title_criteria (title_id, group_id, elo)
+VALUES (?1, ?2, ?3)
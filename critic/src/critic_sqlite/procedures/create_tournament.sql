@@ -0,0 +1,2 @@
+INSERT INTO tournament (criterion_id, rounds)
+VALUES (?1, ?2)
@@ -0,0 +1,2 @@
+DELETE FROM exclusions
+WHERE title_id = ?1 AND criterion_id IS ?2
@@ -0,0 +1 @@
+DELETE FROM match_history WHERE id = ?1
@@ -0,0 +1,3 @@
+DELETE FROM match_history
+WHERE criterion_id = ?1
+    AND ((a_id = ?2 AND b_id = ?3) OR (a_id = ?3 AND b_id = ?2))
@@ -0,0 +1,2 @@
+DELETE FROM title_fields
+WHERE title_id = ?1 AND key = ?2
@@ -0,0 +1 @@
+DELETE FROM tournament WHERE id = ?1
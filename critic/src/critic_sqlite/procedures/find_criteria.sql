@@ -1,3 +1,3 @@
-SELECT id, value FROM criteria
+SELECT id, value, weight FROM criteria
 WHERE group_id == ?1
 ORDER BY value ASC
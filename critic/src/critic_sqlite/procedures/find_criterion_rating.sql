@@ -0,0 +1,2 @@
+SELECT elo, deviation, volatility, matches FROM title_criterion
+WHERE title_id = ?1 AND criterion_id = ?2
@@ -0,0 +1,2 @@
+SELECT elo, deviation, volatility, matches FROM title_criteria
+WHERE title_id = ?1 AND group_id = ?2
@@ -0,0 +1,2 @@
+SELECT CAST(value AS TEXT) FROM settings
+WHERE key = ?1
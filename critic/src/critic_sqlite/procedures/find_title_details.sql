@@ -0,0 +1,4 @@
+SELECT t.id, t.name, d.year, d.platform, d.creator, d.notes
+FROM titles t
+LEFT JOIN title_details d ON d.title_id = t.id
+WHERE t.id = ?1
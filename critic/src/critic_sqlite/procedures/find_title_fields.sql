@@ -0,0 +1,3 @@
+SELECT key, value FROM title_fields
+WHERE title_id = ?1
+ORDER BY key
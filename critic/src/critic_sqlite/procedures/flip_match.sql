@@ -0,0 +1 @@
+UPDATE match_history SET score = 1.0 - score WHERE id = ?1
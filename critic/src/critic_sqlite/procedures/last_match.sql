@@ -0,0 +1,6 @@
+SELECT mh.id, c.group_id, mh.criterion_id, mh.a_id, mh.b_id,
+    mh.elo_adj_a, mh.elo_adj_b, mh.criterion_adj_a, mh.criterion_adj_b
+FROM match_history mh
+JOIN criteria c ON c.id = mh.criterion_id
+ORDER BY mh.time DESC, mh.id DESC
+LIMIT 1
@@ -0,0 +1 @@
+UPDATE match_history SET pairing_id = ?2 WHERE id = ?1
@@ -0,0 +1,8 @@
+SELECT mh.id, c.group_id, mh.criterion_id, mh.a_id, mh.b_id, mh.score,
+    mh.elo_adj_a, mh.elo_adj_b, mh.criterion_adj_a, mh.criterion_adj_b, mh.time
+FROM match_history mh
+JOIN criteria c ON c.id = mh.criterion_id
+WHERE (?1 IS NULL OR mh.a_id = ?1 OR mh.b_id = ?1)
+    AND (?2 IS NULL OR mh.criterion_id = ?2)
+ORDER BY mh.time DESC, mh.id DESC
+LIMIT ?3 OFFSET ?4
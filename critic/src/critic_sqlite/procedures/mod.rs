@@ -1,5 +1,3 @@
-pub const CREATE: &str = include_str!("create.sql");
-
 pub const ALL_GROUPS: &str = include_str!("all_groups.sql");
 pub const ADD_GROUP_TO_ALL: &str = include_str!("add_group_to_all.sql");
 pub const CREATE_GROUP_CRITERIA: &str = include_str!("create_group_criteria.sql");
@@ -10,6 +8,7 @@ pub const FIND_TITLES_BY_GROUP: &str = include_str!("find_titles_by_group.sql");
 
 pub const CREATE_CRITERION: &str = include_str!("create_criterion.sql");
 pub const UPDATE_CRITERION: &str = include_str!("update_criterion.sql");
+pub const UPDATE_CRITERION_WEIGHT: &str = include_str!("update_criterion_weight.sql");
 pub const DELETE_CRITERION: &str = include_str!("delete_criterion.sql");
 pub const FIND_CRITERIA: &str = include_str!("find_criteria.sql");
 
@@ -19,8 +18,47 @@ pub const UPDATE_TITLE: &str = include_str!("update_title.sql");
 pub const DELETE_TITLE: &str = include_str!("delete_title.sql");
 pub const CREATE_TITLE_CRITERIA: &str = include_str!("create_title_criteria.sql");
 pub const DELETE_TITLE_CRITERIA: &str = include_str!("delete_title_criteria.sql");
+pub const FIND_TITLE_DETAILS: &str = include_str!("find_title_details.sql");
+pub const FIND_TITLE_FIELDS: &str = include_str!("find_title_fields.sql");
+pub const UPDATE_TITLE_DETAILS: &str = include_str!("update_title_details.sql");
+pub const SET_TITLE_FIELD: &str = include_str!("set_title_field.sql");
+pub const DELETE_TITLE_FIELD: &str = include_str!("delete_title_field.sql");
 
 pub const TOP_CRITERIA: &str = include_str!("top_criteria.sql");
+pub const TOP_CRITERION: &str = include_str!("top_criterion.sql");
+pub const TOP_COMPOSITE: &str = include_str!("top_composite.sql");
 pub const NEXT_CONTEST: &str = include_str!("next_contest.sql");
+pub const ADD_SKIP: &str = include_str!("add_skip.sql");
+pub const RECENT_SKIPS: &str = include_str!("recent_skips.sql");
 pub const ADD_CONTEST_RESULT: &str = include_str!("add_contest_result.sql");
-pub const UPDATE_ELO: &str = include_str!("update_elo.sql");
+pub const ALL_MATCHES: &str = include_str!("all_matches.sql");
+pub const LAST_MATCH: &str = include_str!("last_match.sql");
+pub const DELETE_MATCH: &str = include_str!("delete_match.sql");
+pub const DELETE_PAIR_MATCHES: &str = include_str!("delete_pair_matches.sql");
+pub const FLIP_MATCH: &str = include_str!("flip_match.sql");
+pub const MATCH_HISTORY: &str = include_str!("match_history.sql");
+pub const REVERT_RATING: &str = include_str!("revert_rating.sql");
+pub const REVERT_CRITERION_RATING: &str = include_str!("revert_criterion_rating.sql");
+pub const UPDATE_MATCH_ADJUSTMENT: &str = include_str!("update_match_adjustment.sql");
+pub const ALL_RATINGS: &str = include_str!("all_ratings.sql");
+pub const FIND_RATING: &str = include_str!("find_rating.sql");
+pub const FIND_CRITERION_RATING: &str = include_str!("find_criterion_rating.sql");
+pub const UPDATE_RATING: &str = include_str!("update_rating.sql");
+pub const UPDATE_CRITERION_RATING: &str = include_str!("update_criterion_rating.sql");
+
+pub const ALL_EXCLUSIONS: &str = include_str!("all_exclusions.sql");
+pub const ADD_EXCLUSION: &str = include_str!("add_exclusion.sql");
+pub const DELETE_EXCLUSION: &str = include_str!("delete_exclusion.sql");
+
+pub const CREATE_TOURNAMENT: &str = include_str!("create_tournament.sql");
+pub const DELETE_TOURNAMENT: &str = include_str!("delete_tournament.sql");
+pub const ACTIVE_TOURNAMENT: &str = include_str!("active_tournament.sql");
+pub const TOURNAMENT_PAIRINGS: &str = include_str!("tournament_pairings.sql");
+pub const CREATE_PAIRING: &str = include_str!("create_pairing.sql");
+pub const UPDATE_TOURNAMENT_ROUND: &str = include_str!("update_tournament_round.sql");
+pub const SCORE_PAIRING: &str = include_str!("score_pairing.sql");
+pub const LINK_PAIRING: &str = include_str!("link_pairing.sql");
+pub const UNSCORE_PAIRING: &str = include_str!("unscore_pairing.sql");
+
+pub const FIND_SETTING: &str = include_str!("find_setting.sql");
+pub const UPDATE_SETTING: &str = include_str!("update_setting.sql");
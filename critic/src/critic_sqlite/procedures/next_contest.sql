@@ -4,9 +4,11 @@ unevaluated_pairs AS (
         tc1.title_id AS title1_id,
         tc2.title_id AS title2_id,
         c.id as criterion_id,
-        ABS(tc1.elo - tc2.elo) AS elo_distance,
+        ABS(COALESCE(tcr1.elo, ?2) - COALESCE(tcr2.elo, ?2)) AS elo_distance,
         tc1.elo as t1_elo,
         tc2.elo as t2_elo,
+        COALESCE(tcr1.elo, ?2) as t1_criterion_elo,
+        COALESCE(tcr2.elo, ?2) as t2_criterion_elo,
         RANDOM() as rng
     FROM title_criteria tc1
     JOIN title_criteria tc2 
@@ -15,6 +17,10 @@ unevaluated_pairs AS (
     JOIN titles t1 ON tc1.title_id = t1.id
     JOIN titles t2 ON tc2.title_id = t2.id
     JOIN criteria c ON c.group_id = tc1.group_id
+    LEFT JOIN title_criterion tcr1
+        ON tcr1.title_id = tc1.title_id AND tcr1.criterion_id = c.id
+    LEFT JOIN title_criterion tcr2
+        ON tcr2.title_id = tc2.title_id AND tcr2.criterion_id = c.id
     LEFT JOIN match_history mh ON 
         (
             (mh.a_id = tc1.title_id AND mh.b_id = tc2.title_id) OR
@@ -22,6 +28,20 @@ unevaluated_pairs AS (
         ) 
         AND mh.criterion_id = c.id
     WHERE mh.id IS NULL
+        AND NOT EXISTS (
+            SELECT 1 FROM skip_history sh
+            WHERE sh.criterion_id = c.id
+                AND (
+                    (sh.a_id = tc1.title_id AND sh.b_id = tc2.title_id) OR
+                    (sh.a_id = tc2.title_id AND sh.b_id = tc1.title_id)
+                )
+                AND sh.time > datetime('now', '-' || ?1 || ' hours')
+        )
+        AND NOT EXISTS (
+            SELECT 1 FROM exclusions ex
+            WHERE ex.title_id IN (tc1.title_id, tc2.title_id)
+                AND (ex.criterion_id IS NULL OR ex.criterion_id = c.id)
+        )
 ),
 next_comparison AS (
     SELECT 
@@ -29,7 +49,9 @@ next_comparison AS (
         title2_id, 
         criterion_id,
         t1_elo,
-        t2_elo
+        t2_elo,
+        t1_criterion_elo,
+        t2_criterion_elo
     FROM unevaluated_pairs
     ORDER BY elo_distance ASC, rng ASC
     LIMIT 1
@@ -40,7 +62,9 @@ SELECT
     c.group_id AS criteria_group,
     c.id AS criterion_id,
     c.value AS criterion_name,
-    cg.value AS criteria_group
+    cg.value AS criteria_group,
+    nc.t1_criterion_elo,
+    nc.t2_criterion_elo
 FROM next_comparison nc
 JOIN titles t1 ON nc.title1_id = t1.id
 JOIN titles t2 ON nc.title2_id = t2.id
@@ -0,0 +1,4 @@
+SELECT criterion_id, a_id, b_id, reason, time
+FROM skip_history
+WHERE time > datetime('now', '-' || ?1 || ' hours')
+ORDER BY time ASC, id ASC
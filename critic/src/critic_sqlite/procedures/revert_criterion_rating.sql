@@ -0,0 +1 @@
+UPDATE title_criterion SET elo = elo - ?3, matches = MAX(matches - 1, 0) WHERE title_id = ?1 AND criterion_id = ?2
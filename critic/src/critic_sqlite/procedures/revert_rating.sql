@@ -0,0 +1 @@
+UPDATE title_criteria SET elo = elo - ?3, matches = MAX(matches - 1, 0) WHERE title_id = ?1 AND group_id = ?2
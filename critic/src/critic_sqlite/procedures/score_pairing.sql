@@ -0,0 +1 @@
+UPDATE tournament_pairing SET score = ?2 WHERE id = ?1
@@ -0,0 +1,3 @@
+INSERT INTO title_fields (title_id, key, value)
+VALUES (?1, ?2, ?3)
+ON CONFLICT (title_id, key) DO UPDATE SET value = excluded.value
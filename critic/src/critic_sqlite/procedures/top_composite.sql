@@ -0,0 +1,19 @@
+SELECT cg.value, t.name,
+    COALESCE(
+        SUM(c.weight * COALESCE(tcr.elo, ?4)) / NULLIF(SUM(c.weight), 0.0),
+        tc.elo
+    ) AS score,
+    COALESCE(
+        SUM(c.weight * COALESCE(tcr.deviation, ?5)) / NULLIF(SUM(c.weight), 0.0),
+        tc.deviation
+    ),
+    tc.matches
+FROM title_criteria tc
+JOIN titles t ON t.id == tc.title_id
+JOIN criteria_group cg ON cg.id == tc.group_id
+JOIN criteria c ON c.group_id == tc.group_id
+LEFT JOIN title_criterion tcr ON tcr.title_id == tc.title_id AND tcr.criterion_id == c.id
+WHERE ?1 IS NULL OR cg.value == ?1
+GROUP BY tc.id
+ORDER BY cg.value ASC, score DESC
+LIMIT ?2 OFFSET ?3
@@ -1,4 +1,4 @@
-SELECT c.value, t.name, tc.elo FROM title_criteria tc
+SELECT c.value, t.name, tc.elo, tc.deviation, tc.matches FROM title_criteria tc
 JOIN titles t ON t.id == tc.title_id
 JOIN criteria_group c ON c.id == tc.group_id
 WHERE ?1 IS NULL OR c.value == ?1
@@ -0,0 +1,9 @@
+SELECT cg.value, t.name, COALESCE(tcr.elo, ?4) AS elo, COALESCE(tcr.deviation, ?5),
+    COALESCE(tcr.matches, 0) FROM title_criteria tc
+JOIN titles t ON t.id == tc.title_id
+JOIN criteria c ON c.group_id == tc.group_id
+JOIN criteria_group cg ON cg.id == tc.group_id
+LEFT JOIN title_criterion tcr ON tcr.title_id == tc.title_id AND tcr.criterion_id == c.id
+WHERE c.id == ?1
+ORDER BY elo DESC, t.name ASC
+LIMIT ?2 OFFSET ?3
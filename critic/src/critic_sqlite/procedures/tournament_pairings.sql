@@ -0,0 +1,4 @@
+SELECT id, tournament_id, round, a_id, b_id, score
+FROM tournament_pairing
+WHERE tournament_id = ?1
+ORDER BY round ASC, id ASC
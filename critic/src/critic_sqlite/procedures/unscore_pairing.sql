@@ -0,0 +1,2 @@
+UPDATE tournament_pairing SET score = NULL
+WHERE id = (SELECT pairing_id FROM match_history WHERE id = ?1)
@@ -0,0 +1,5 @@
+INSERT INTO title_criterion (title_id, criterion_id, elo, deviation, volatility, matches)
+VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+ON CONFLICT (title_id, criterion_id) DO UPDATE
+SET elo = excluded.elo, deviation = excluded.deviation, volatility = excluded.volatility,
+    matches = excluded.matches
@@ -0,0 +1 @@
+UPDATE criteria SET weight = ?2 WHERE id = ?1
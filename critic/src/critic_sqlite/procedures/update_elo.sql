@@ -1 +0,0 @@
-UPDATE title_criteria SET elo = elo + ?3 WHERE title_id = ?1 AND group_id = ?2
@@ -0,0 +1,3 @@
+UPDATE match_history
+SET elo_adj_a = ?2, elo_adj_b = ?3, criterion_adj_a = ?4, criterion_adj_b = ?5
+WHERE id = ?1
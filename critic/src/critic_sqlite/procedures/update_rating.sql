@@ -0,0 +1,2 @@
+UPDATE title_criteria SET elo = ?3, deviation = ?4, volatility = ?5, matches = ?6
+WHERE title_id = ?1 AND group_id = ?2
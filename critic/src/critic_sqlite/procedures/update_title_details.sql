@@ -0,0 +1,7 @@
+INSERT INTO title_details (title_id, year, platform, creator, notes)
+VALUES (?1, ?2, ?3, ?4, ?5)
+ON CONFLICT (title_id) DO UPDATE SET
+    year = excluded.year,
+    platform = excluded.platform,
+    creator = excluded.creator,
+    notes = excluded.notes
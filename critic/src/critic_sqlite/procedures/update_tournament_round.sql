@@ -0,0 +1 @@
+UPDATE tournament SET round = ?2 WHERE id = ?1
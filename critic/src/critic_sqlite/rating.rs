@@ -0,0 +1,53 @@
+use rusqlite::{params, Connection, Transaction};
+
+use crate::{dto::UpdateRatings, DbError, Record};
+
+use super::procedures;
+
+impl Record<Connection> for UpdateRatings {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        let tx = connection.transaction()?;
+        update_ratings(&tx, self)?;
+
+        tx.commit()
+            .map_err(DbError::from)
+            .map(|_| self.ratings.len())
+    }
+}
+
+/// Writes `update` as part of a larger transaction.
+pub(super) fn update_ratings(tx: &Transaction, update: &UpdateRatings) -> Result<(), DbError> {
+    let mut update_stmt = tx.prepare(procedures::UPDATE_RATING)?;
+
+    let mut update_criterion_stmt = tx.prepare(procedures::UPDATE_CRITERION_RATING)?;
+
+    let mut adjust_stmt = tx.prepare(procedures::UPDATE_MATCH_ADJUSTMENT)?;
+
+    for x in &update.ratings {
+        let (stmt, id) = match x.criterion {
+            Some(criterion) => (&mut update_criterion_stmt, criterion),
+            None => (&mut update_stmt, x.group),
+        };
+
+        stmt.execute(params![
+            x.title,
+            id,
+            x.rating.elo,
+            x.rating.deviation,
+            x.rating.volatility,
+            x.rating.matches,
+        ])?;
+    }
+
+    for x in &update.adjustments {
+        adjust_stmt.execute(params![
+            x.id,
+            x.elo_adj_a,
+            x.elo_adj_b,
+            x.criterion_adj_a,
+            x.criterion_adj_b,
+        ])?;
+    }
+
+    Ok(())
+}
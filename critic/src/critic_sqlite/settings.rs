@@ -0,0 +1,100 @@
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::{
+    dto::{SetContestStrategy, SetEloConfig, SetRatingSystem, SetScoreScale, SetSkipCooldown},
+    elo, DbError, Record,
+};
+
+use super::procedures;
+
+pub const RATING_SYSTEM: &str = "rating_system";
+pub const SKIP_COOLDOWN: &str = "skip_cooldown";
+pub const CONTEST_STRATEGY: &str = "contest_strategy";
+pub const SCORE_SCALE: &str = "score_scale";
+pub const ELO_K_SCHEDULE: &str = "elo_k_schedule";
+pub const ELO_K_DEFAULT: &str = "elo_k_default";
+pub const ELO_PROVISIONAL_MATCHES: &str = "elo_provisional_matches";
+pub const ELO_PROVISIONAL_K: &str = "elo_provisional_k";
+pub const ELO_FLOOR: &str = "elo_floor";
+pub const ELO_BASELINE: &str = "elo_baseline";
+
+pub fn find_setting(connection: &Connection, key: &str) -> Result<Option<String>, DbError> {
+    let mut stmt = connection.prepare(procedures::FIND_SETTING)?;
+
+    stmt.query_row(params![key], |r| r.get(0))
+        .optional()
+        .map_err(DbError::from)
+}
+
+impl Record<Connection> for SetRatingSystem {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        connection
+            .execute(
+                procedures::UPDATE_SETTING,
+                params![RATING_SYSTEM, self.system.as_str()],
+            )
+            .map_err(DbError::from)
+    }
+}
+
+impl Record<Connection> for SetSkipCooldown {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        connection
+            .execute(
+                procedures::UPDATE_SETTING,
+                params![SKIP_COOLDOWN, self.hours.to_string()],
+            )
+            .map_err(DbError::from)
+    }
+}
+
+impl Record<Connection> for SetContestStrategy {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        connection
+            .execute(
+                procedures::UPDATE_SETTING,
+                params![CONTEST_STRATEGY, self.strategy.as_str()],
+            )
+            .map_err(DbError::from)
+    }
+}
+
+impl Record<Connection> for SetScoreScale {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        connection
+            .execute(
+                procedures::UPDATE_SETTING,
+                params![SCORE_SCALE, self.scale.as_str()],
+            )
+            .map_err(DbError::from)
+    }
+}
+
+impl Record<Connection> for SetEloConfig {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        let tx = connection.transaction()?;
+
+        {
+            let mut stmt = tx.prepare(procedures::UPDATE_SETTING)?;
+
+            for (key, value) in [
+                (
+                    ELO_K_SCHEDULE,
+                    elo::format_k_schedule(&self.config.k_schedule),
+                ),
+                (ELO_K_DEFAULT, self.config.k_default.to_string()),
+                (
+                    ELO_PROVISIONAL_MATCHES,
+                    self.config.provisional_matches.to_string(),
+                ),
+                (ELO_PROVISIONAL_K, self.config.provisional_k.to_string()),
+                (ELO_FLOOR, self.config.floor.to_string()),
+                (ELO_BASELINE, self.config.baseline.to_string()),
+            ] {
+                stmt.execute(params![key, value])?;
+            }
+        }
+
+        tx.commit().map_err(DbError::from).map(|_| 1)
+    }
+}
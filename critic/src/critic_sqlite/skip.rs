@@ -0,0 +1,40 @@
+use rusqlite::{params, Connection};
+
+use crate::{dto::SkipContest, DbError, Record};
+
+use super::procedures;
+
+impl Record<Connection> for SkipContest {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        connection
+            .execute(
+                procedures::ADD_SKIP,
+                params![self.criterion, self.a, self.b, self.reason],
+            )
+            .map_err(DbError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{critic_sqlite::test_connection, dto::SetSkipCooldown, CriticData, DbConnection};
+
+    #[test]
+    fn skipped_pair_waits_for_cooldown() {
+        let mut conn = test_connection();
+        let contest = conn.next_contest().unwrap();
+
+        conn.save(&SkipContest {
+            criterion: contest.criterion.id,
+            a: contest.b.id,
+            b: contest.a.id,
+            reason: Some("Haven't played".into()),
+        })
+        .unwrap();
+        assert!(conn.next_contest().is_err());
+
+        conn.save(&SetSkipCooldown { hours: 0 }).unwrap();
+        assert_eq!(conn.next_contest().unwrap(), contest);
+    }
+}
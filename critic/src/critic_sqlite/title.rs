@@ -1,106 +1,186 @@
 use rusqlite::{params, Connection};
 
 use crate::{
-    dto::{DeleteTitle, DeleteTitleCriteria, NewTitle, NewTitleCriteria, UpdateTitle},
-    DbError, Record,
+    dto::{
+        DeleteTitle, DeleteTitleCriteria, DeleteTitleField, NewTitle, NewTitleCriteria,
+        SetTitleField, UpdateTitle, UpdateTitleDetails,
+    },
+    CriticData, DbError, Record,
 };
 
 use super::procedures;
 
 impl Record<Connection> for NewTitle {
     fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
-        let tx = connection
-            .transaction()
-            .expect("Save transaction could not be started");
+        let tx = connection.transaction()?;
 
         let id = {
-            let mut stmt = tx
-                .prepare(procedures::CREATE_TITLE)
-                .expect("Failed to prepare statement");
+            let mut stmt = tx.prepare(procedures::CREATE_TITLE)?;
 
-            stmt.execute(params![self.name]).map_err(DbError::Sqlite)?;
+            stmt.execute(params![self.name])?;
             tx.last_insert_rowid() as usize
         };
 
-        tx.commit().map_err(DbError::Sqlite).map(|_| id)
+        tx.commit().map_err(DbError::from).map(|_| id)
     }
 }
 
 impl Record<Connection> for UpdateTitle {
     fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
-        let tx = connection
-            .transaction()
-            .expect("Save transaction could not be started");
+        let tx = connection.transaction()?;
 
         {
-            let mut stmt = tx
-                .prepare(procedures::UPDATE_TITLE)
-                .expect("Failed to prepare statement");
+            let mut stmt = tx.prepare(procedures::UPDATE_TITLE)?;
 
-            stmt.execute(params![self.id, self.name])
-                .map_err(DbError::Sqlite)?;
+            stmt.execute(params![self.id, self.name])?;
         }
 
-        tx.commit().map_err(DbError::Sqlite).map(|_| 1)
+        tx.commit().map_err(DbError::from).map(|_| 1)
     }
 }
 
 impl Record<Connection> for NewTitleCriteria {
     fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
-        let tx = connection
-            .transaction()
-            .expect("Save transaction could not be started");
+        let baseline = connection.elo_config()?.baseline;
+        let tx = connection.transaction()?;
 
         let id = {
-            let mut stmt = tx
-                .prepare(procedures::CREATE_TITLE_CRITERIA)
-                .expect("Failed to prepare statement");
+            let mut stmt = tx.prepare(procedures::CREATE_TITLE_CRITERIA)?;
 
-            stmt.execute(params![self.title, self.criteria])
-                .map_err(DbError::Sqlite)?;
+            stmt.execute(params![self.title, self.criteria, baseline])?;
 
             tx.last_insert_rowid() as usize
         };
 
-        tx.commit().map_err(DbError::Sqlite).map(|_| id)
+        tx.commit().map_err(DbError::from).map(|_| id)
     }
 }
 
 impl Record<Connection> for DeleteTitle {
     fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
-        let tx = connection
-            .transaction()
-            .expect("Save transaction could not be started");
+        let tx = connection.transaction()?;
 
         let id = {
-            let mut stmt = tx
-                .prepare(procedures::DELETE_TITLE)
-                .expect("Failed to prepare statement");
+            let mut stmt = tx.prepare(procedures::DELETE_TITLE)?;
 
-            stmt.execute(params![self.id]).map_err(DbError::Sqlite)?;
+            stmt.execute(params![self.id])?;
             tx.last_insert_rowid() as usize
         };
 
-        tx.commit().map_err(DbError::Sqlite).map(|_| id)
+        tx.commit().map_err(DbError::from).map(|_| id)
     }
 }
 
 impl Record<Connection> for DeleteTitleCriteria {
     fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
-        let tx = connection
-            .transaction()
-            .expect("Save transaction could not be started");
+        let tx = connection.transaction()?;
 
         let id = {
-            let mut stmt = tx
-                .prepare(procedures::DELETE_TITLE_CRITERIA)
-                .expect("Failed to prepare statement");
+            let mut stmt = tx.prepare(procedures::DELETE_TITLE_CRITERIA)?;
 
-            stmt.execute(params![self.title, self.criteria])
-                .map_err(DbError::Sqlite)?;
+            stmt.execute(params![self.title, self.criteria])?;
             tx.last_insert_rowid() as usize
         };
 
-        tx.commit().map_err(DbError::Sqlite).map(|_| id)
+        tx.commit().map_err(DbError::from).map(|_| id)
+    }
+}
+
+impl Record<Connection> for UpdateTitleDetails {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        connection
+            .execute(
+                procedures::UPDATE_TITLE_DETAILS,
+                params![
+                    self.title,
+                    self.year,
+                    self.platform,
+                    self.creator,
+                    self.notes
+                ],
+            )
+            .map_err(DbError::from)
+    }
+}
+
+impl Record<Connection> for SetTitleField {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        connection
+            .execute(
+                procedures::SET_TITLE_FIELD,
+                params![self.title, self.key, self.value],
+            )
+            .map_err(DbError::from)
+    }
+}
+
+impl Record<Connection> for DeleteTitleField {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        connection
+            .execute(
+                procedures::DELETE_TITLE_FIELD,
+                params![self.title, self.key],
+            )
+            .map_err(DbError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{critic_sqlite::test_connection, DbConnection};
+
+    #[test]
+    fn reports_duplicates_and_missing_rows() {
+        let mut conn = test_connection();
+        let renamed = conn.save(&UpdateTitle {
+            id: 2,
+            name: "A".to_string(),
+        });
+        assert!(matches!(renamed, Err(DbError::Constraint(_))));
+
+        conn.save(&DeleteTitle { id: 2 }).unwrap();
+        assert!(matches!(conn.next_contest(), Err(DbError::NotFound)));
+    }
+
+    #[test]
+    fn stores_title_metadata() {
+        let mut conn = test_connection();
+        conn.save(&UpdateTitleDetails {
+            title: 1,
+            year: Some(1997),
+            platform: Some("PlayStation".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        for (key, value) in [("igdb", "427"), ("mobygames", "1"), ("mobygames", "2")] {
+            conn.save(&SetTitleField {
+                title: 1,
+                key: key.to_string(),
+                value: value.to_string(),
+            })
+            .unwrap();
+        }
+
+        let details = conn.title_details(1).unwrap();
+        assert_eq!(details.summary(), "1997 · PlayStation");
+        assert_eq!(
+            details.fields.iter().map(|x| &x.value).collect::<Vec<_>>(),
+            ["427", "2"]
+        );
+
+        let removed = conn.save(&DeleteTitleField {
+            title: 1,
+            key: "igdb".to_string(),
+        });
+        assert_eq!(removed.unwrap(), 1);
+        let missing = conn.save(&UpdateTitleDetails {
+            title: 99,
+            ..Default::default()
+        });
+        assert!(matches!(missing, Err(DbError::Constraint(_))));
+
+        conn.save(&DeleteTitle { id: 1 }).unwrap();
+        assert!(matches!(conn.title_details(1), Err(DbError::NotFound)));
     }
 }
@@ -0,0 +1,141 @@
+use rusqlite::{params, Connection};
+
+use crate::{
+    dto::{
+        DeleteTournament, MatchResult, NewRound, NewTournament, ScorePairing, ScoreTournamentMatch,
+    },
+    DbError, Record,
+};
+
+use super::{match_result, procedures};
+
+impl Record<Connection> for NewTournament {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        connection
+            .execute(
+                procedures::CREATE_TOURNAMENT,
+                params![self.criterion, self.rounds],
+            )
+            .map_err(DbError::from)
+            .map(|_| connection.last_insert_rowid() as usize)
+    }
+}
+
+impl Record<Connection> for DeleteTournament {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        connection
+            .execute(procedures::DELETE_TOURNAMENT, params![self.id])
+            .map_err(DbError::from)
+    }
+}
+
+impl Record<Connection> for NewRound {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        let tx = connection.transaction()?;
+
+        {
+            let mut stmt = tx.prepare(procedures::CREATE_PAIRING)?;
+
+            for (a, b) in &self.pairings {
+                stmt.execute(params![self.tournament, self.round, a, b])?;
+            }
+
+            tx.execute(
+                procedures::UPDATE_TOURNAMENT_ROUND,
+                params![self.tournament, self.round],
+            )?;
+        }
+
+        tx.commit()
+            .map_err(DbError::from)
+            .map(|_| self.pairings.len())
+    }
+}
+
+impl Record<Connection> for ScorePairing {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        connection
+            .execute(procedures::SCORE_PAIRING, params![self.id, self.score])
+            .map_err(DbError::from)
+    }
+}
+
+impl Record<Connection> for ScoreTournamentMatch {
+    fn save(&self, connection: &mut Connection) -> Result<usize, DbError> {
+        let tx = connection.transaction()?;
+
+        let id = match_result::add_match(
+            &tx,
+            &MatchResult {
+                criteria_group: self.criteria_group,
+                criterion: self.criterion,
+                a: self.a,
+                b: self.b,
+                score: self.score,
+            },
+        )?;
+        tx.execute(procedures::LINK_PAIRING, params![id, self.pairing])?;
+        tx.execute(procedures::SCORE_PAIRING, params![self.pairing, self.score])?;
+
+        tx.commit().map_err(DbError::from).map(|_| 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{critic_sqlite::test_connection, dto::UndoLastMatch, CriticData, DbConnection};
+
+    /// A one-round tournament with titles 1 and 2 paired.
+    fn paired() -> (Connection, ScoreTournamentMatch) {
+        let mut conn = test_connection();
+        conn.save(&NewTournament {
+            criterion: 1,
+            rounds: 1,
+        })
+        .unwrap();
+        conn.save(&NewRound {
+            tournament: 1,
+            round: 1,
+            pairings: vec![(1, Some(2))],
+        })
+        .unwrap();
+
+        let record = ScoreTournamentMatch {
+            pairing: 1,
+            criteria_group: 1,
+            criterion: 1,
+            a: 1,
+            b: 2,
+            score: 1.0,
+        };
+        (conn, record)
+    }
+
+    #[test]
+    fn failed_score_keeps_match_unrecorded() {
+        let (mut conn, record) = paired();
+        conn.execute_batch(
+            "CREATE TEMP TRIGGER fail_score BEFORE UPDATE ON tournament_pairing
+            BEGIN SELECT RAISE(ABORT, 'fail'); END;",
+        )
+        .unwrap();
+
+        assert!(conn.save(&record).is_err());
+        assert!(conn.matches().unwrap().is_empty());
+        assert_eq!(conn.tournament_pairings(1).unwrap()[0].score, None);
+    }
+
+    #[test]
+    fn undo_unscores_pairing() {
+        let (mut conn, record) = paired();
+        conn.save(&record).unwrap();
+        assert_eq!(conn.tournament_pairings(1).unwrap()[0].score, Some(1.0));
+        assert_eq!(conn.active_tournament().unwrap(), None);
+
+        assert_eq!(conn.save(&UndoLastMatch).unwrap(), 1);
+        assert!(conn.matches().unwrap().is_empty());
+        assert_eq!(conn.tournament_pairings(1).unwrap()[0].score, None);
+        assert_eq!(conn.active_tournament().unwrap().map(|x| x.id), Some(1));
+    }
+}
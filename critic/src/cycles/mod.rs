@@ -0,0 +1,371 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use crate::{dto::MatchRecord, CriticData, DbError};
+
+/// Components larger than this fall back to a greedy ordering instead of an
+/// exact search.
+const EXACT_LIMIT: usize = 16;
+
+/// The net outcome of every match between two titles for one criterion.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct Preference {
+    pub criterion: i32,
+    pub winner: i32,
+    pub loser: i32,
+    /// Net wins for `winner`, where a graded score counts as a partial win.
+    pub margin: f32,
+    /// Ids of the matches behind the preference.
+    pub matches: Vec<i32>,
+}
+
+/// A judgment that contradicts the rest of the history for its criterion.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct Conflict {
+    pub preference: Preference,
+    /// A cycle through the judgment: each title is preferred over the next
+    /// and the last over the first, starting with the judgment's winner and
+    /// loser.
+    pub cycle: Vec<i32>,
+}
+
+/// Nets `matches` into one preference per pair and criterion. Pairs that
+/// came out even have no preference.
+pub fn preferences(matches: &[MatchRecord]) -> Vec<Preference> {
+    let mut pairs: BTreeMap<(i32, i32, i32), (f32, Vec<i32>)> = BTreeMap::new();
+    for m in matches {
+        if m.a == m.b {
+            continue;
+        }
+        let (key, score) = if m.a < m.b {
+            ((m.criterion, m.a, m.b), m.score)
+        } else {
+            ((m.criterion, m.b, m.a), 1.0 - m.score)
+        };
+        let entry = pairs.entry(key).or_default();
+        entry.0 += 2.0 * (score - 0.5);
+        entry.1.push(m.id);
+    }
+
+    pairs
+        .into_iter()
+        .filter(|(_, (margin, _))| margin.abs() > f32::EPSILON)
+        .map(|((criterion, a, b), (margin, matches))| {
+            let (winner, loser) = if margin > 0.0 { (a, b) } else { (b, a) };
+            Preference {
+                criterion,
+                winner,
+                loser,
+                margin: margin.abs(),
+                matches,
+            }
+        })
+        .collect()
+}
+
+/// Finds the fewest, weakest judgments whose reversal would leave every
+/// criterion free of cycles such as A > B > C > A.
+///
+/// Components of up to 16 titles are solved exactly; larger ones use the
+/// Eades–Lin–Smyth heuristic.
+pub fn find_conflicts(matches: &[MatchRecord]) -> Vec<Conflict> {
+    let mut by_criterion: BTreeMap<i32, Vec<Preference>> = BTreeMap::new();
+    for p in preferences(matches) {
+        by_criterion.entry(p.criterion).or_default().push(p);
+    }
+
+    let mut conflicts = Vec::new();
+    for preferences in by_criterion.values() {
+        let graph = Graph::new(preferences);
+        for component in graph.components() {
+            if component.len() < 3 {
+                continue;
+            }
+
+            let order = if component.len() <= EXACT_LIMIT {
+                graph.exact_order(&component)
+            } else {
+                graph.greedy_order(&component)
+            };
+            let rank: HashMap<usize, usize> =
+                order.iter().enumerate().map(|(i, x)| (*x, i)).collect();
+
+            for &(u, v, edge) in &graph.edges {
+                let (Some(ru), Some(rv)) = (rank.get(&u), rank.get(&v)) else {
+                    continue;
+                };
+                if ru < rv {
+                    continue;
+                }
+                let path = graph.path(v, u, &rank);
+                let mut cycle = vec![graph.titles[u]];
+                cycle.extend(path[..path.len() - 1].iter().map(|x| graph.titles[*x]));
+                conflicts.push(Conflict {
+                    preference: preferences[edge].clone(),
+                    cycle,
+                });
+            }
+        }
+    }
+
+    conflicts
+}
+
+/// Every conflict in the recorded match history.
+pub fn conflicts<T: CriticData>(connection: &T) -> Result<Vec<Conflict>, DbError> {
+    Ok(find_conflicts(&connection.matches()?))
+}
+
+/// Preferences of one criterion as a directed graph from winner to loser.
+struct Graph {
+    titles: Vec<i32>,
+    /// `(winner, loser, preference)` by node index.
+    edges: Vec<(usize, usize, usize)>,
+    weights: Vec<Vec<f32>>,
+    out: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    fn new(preferences: &[Preference]) -> Self {
+        let mut titles: Vec<i32> = preferences
+            .iter()
+            .flat_map(|x| [x.winner, x.loser])
+            .collect();
+        titles.sort_unstable();
+        titles.dedup();
+        let index = |title: i32| titles.binary_search(&title).unwrap_or_default();
+
+        let n = titles.len();
+        let mut edges = Vec::with_capacity(preferences.len());
+        let mut weights = vec![vec![0.0; n]; n];
+        let mut out = vec![Vec::new(); n];
+        for (i, p) in preferences.iter().enumerate() {
+            let (u, v) = (index(p.winner), index(p.loser));
+            edges.push((u, v, i));
+            weights[u][v] = p.margin;
+            out[u].push(v);
+        }
+
+        Self {
+            titles,
+            edges,
+            weights,
+            out,
+        }
+    }
+
+    /// Strongly connected components (Tarjan).
+    fn components(&self) -> Vec<Vec<usize>> {
+        struct State {
+            index: Vec<Option<usize>>,
+            low: Vec<usize>,
+            stack: Vec<usize>,
+            on_stack: Vec<bool>,
+            next: usize,
+            components: Vec<Vec<usize>>,
+        }
+
+        fn visit(graph: &Graph, v: usize, s: &mut State) {
+            s.index[v] = Some(s.next);
+            s.low[v] = s.next;
+            s.next += 1;
+            s.stack.push(v);
+            s.on_stack[v] = true;
+
+            for &w in &graph.out[v] {
+                match s.index[w] {
+                    None => {
+                        visit(graph, w, s);
+                        s.low[v] = s.low[v].min(s.low[w]);
+                    }
+                    Some(index) if s.on_stack[w] => s.low[v] = s.low[v].min(index),
+                    Some(_) => {}
+                }
+            }
+
+            if Some(s.low[v]) == s.index[v] {
+                let mut component = Vec::new();
+                while let Some(w) = s.stack.pop() {
+                    s.on_stack[w] = false;
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                s.components.push(component);
+            }
+        }
+
+        let n = self.titles.len();
+        let mut state = State {
+            index: vec![None; n],
+            low: vec![0; n],
+            stack: Vec::new(),
+            on_stack: vec![false; n],
+            next: 0,
+            components: Vec::new(),
+        };
+        for v in 0..n {
+            if state.index[v].is_none() {
+                visit(self, v, &mut state);
+            }
+        }
+        state.components
+    }
+
+    /// Orders `component` so the total margin of judgments pointing backwards
+    /// is as small as possible, by dynamic programming over subsets.
+    fn exact_order(&self, component: &[usize]) -> Vec<usize> {
+        let n = component.len();
+        let full = (1usize << n) - 1;
+        let mut cost = vec![f32::INFINITY; full + 1];
+        let mut last = vec![0; full + 1];
+        cost[0] = 0.0;
+
+        for mask in 0..full {
+            if cost[mask].is_infinite() {
+                continue;
+            }
+            for (i, &v) in component.iter().enumerate() {
+                if mask & (1 << i) != 0 {
+                    continue;
+                }
+                // Placing `v` after everything in `mask` reverses each
+                // judgment `v` won against them.
+                let backwards: f32 = component
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| mask & (1 << j) != 0)
+                    .map(|(_, &u)| self.weights[v][u])
+                    .sum();
+                let next = mask | (1 << i);
+                if cost[mask] + backwards < cost[next] {
+                    cost[next] = cost[mask] + backwards;
+                    last[next] = i;
+                }
+            }
+        }
+
+        let mut order = Vec::with_capacity(n);
+        let mut mask = full;
+        while mask != 0 {
+            let i = last[mask];
+            order.push(component[i]);
+            mask &= !(1 << i);
+        }
+        order.reverse();
+        order
+    }
+
+    /// Eades–Lin–Smyth: peel off sinks and sources, otherwise take the title
+    /// whose wins outweigh its losses the most.
+    fn greedy_order(&self, component: &[usize]) -> Vec<usize> {
+        let mut left: Vec<usize> = component.to_vec();
+        let mut head = Vec::new();
+        let mut tail = Vec::new();
+
+        let flow = |v: usize, left: &[usize]| -> (f32, f32) {
+            left.iter().fold((0.0, 0.0), |(out, inc), &u| {
+                (out + self.weights[v][u], inc + self.weights[u][v])
+            })
+        };
+
+        while !left.is_empty() {
+            if let Some(i) = left.iter().position(|&v| flow(v, &left).0 == 0.0) {
+                tail.push(left.remove(i));
+                continue;
+            }
+            if let Some(i) = left.iter().position(|&v| flow(v, &left).1 == 0.0) {
+                head.push(left.remove(i));
+                continue;
+            }
+            let i = (0..left.len())
+                .max_by(|&a, &b| {
+                    let (oa, ia) = flow(left[a], &left);
+                    let (ob, ib) = flow(left[b], &left);
+                    (oa - ia).total_cmp(&(ob - ib))
+                })
+                .unwrap_or(0);
+            head.push(left.remove(i));
+        }
+
+        tail.reverse();
+        head.extend(tail);
+        head
+    }
+
+    /// Shortest path from `from` to `to` among the titles in `within`.
+    fn path(&self, from: usize, to: usize, within: &HashMap<usize, usize>) -> Vec<usize> {
+        let mut previous: HashMap<usize, usize> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+        while let Some(v) = queue.pop_front() {
+            if v == to {
+                break;
+            }
+            for &w in &self.out[v] {
+                if w != from && within.contains_key(&w) && !previous.contains_key(&w) {
+                    previous.insert(w, v);
+                    queue.push_back(w);
+                }
+            }
+        }
+
+        let mut path = vec![to];
+        while let Some(&v) = previous.get(path.last().unwrap_or(&from)) {
+            path.push(v);
+        }
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: i32, a: i32, b: i32, score: f32) -> MatchRecord {
+        MatchRecord {
+            id,
+            criteria_group: 1,
+            criterion: 1,
+            a,
+            b,
+            score,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn breaks_the_weakest_judgment() {
+        let matches = [
+            record(1, 1, 2, 1.0),
+            record(2, 2, 3, 1.0),
+            record(3, 3, 1, 0.75),
+            record(4, 1, 4, 1.0),
+            record(5, 2, 4, 1.0),
+        ];
+
+        let conflicts = find_conflicts(&matches);
+        assert_eq!(conflicts.len(), 1);
+        let conflict = &conflicts[0];
+        assert_eq!(
+            (conflict.preference.winner, conflict.preference.loser),
+            (3, 1)
+        );
+        assert_eq!(conflict.preference.matches, vec![3]);
+        assert_eq!(conflict.cycle, vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn consistent_history_has_no_conflicts() {
+        let matches = [
+            record(1, 1, 2, 1.0),
+            record(2, 2, 3, 1.0),
+            record(3, 3, 1, 1.0),
+            record(4, 1, 3, 1.0),
+            record(5, 1, 3, 1.0),
+        ];
+
+        assert!(find_conflicts(&matches).is_empty());
+        assert_eq!(preferences(&matches).len(), 3);
+    }
+}
@@ -5,6 +5,7 @@ pub struct Contestant {
     pub id: i32,
     pub name: String,
     pub elo: f32,
+    pub criterion_elo: f32,
 }
 
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
@@ -13,3 +14,48 @@ pub struct Contest {
     pub a: Contestant,
     pub b: Contestant,
 }
+
+/// How the next pair to compare is chosen.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum ContestStrategy {
+    /// The never-compared pair with the smallest rating gap.
+    #[default]
+    Closest,
+    /// The pair whose outcome is expected to teach the most about the
+    /// ratings, rematching pairs once every pair has been compared.
+    InformationGain,
+}
+
+impl ContestStrategy {
+    pub const ALL: [ContestStrategy; 2] =
+        [ContestStrategy::Closest, ContestStrategy::InformationGain];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContestStrategy::Closest => "closest",
+            ContestStrategy::InformationGain => "information-gain",
+        }
+    }
+}
+
+impl std::fmt::Display for ContestStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ContestStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ContestStrategy::ALL
+            .into_iter()
+            .find(|x| x.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown contest strategy '{s}'"))
+    }
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct SetContestStrategy {
+    pub strategy: ContestStrategy,
+}
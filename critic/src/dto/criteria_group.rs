@@ -8,6 +8,7 @@ pub struct CriteriaGroup {
 pub struct CriteriaGroupItem {
     pub id: i32,
     pub name: String,
+    pub weight: f32,
 }
 
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
@@ -12,6 +12,12 @@ pub struct UpdateCriterion {
     pub name: String,
 }
 
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct UpdateCriterionWeight {
+    pub id: i32,
+    pub weight: f32,
+}
+
 #[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 pub struct NewCriterion {
     pub group: i32,
@@ -0,0 +1,20 @@
+/// A title the rater hasn't experienced, either entirely or for a single
+/// criterion when `criterion` is set.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct Exclusion {
+    pub title: i32,
+    pub criterion: Option<i32>,
+}
+
+/// Keeps a title out of contests while still listing it in the rankings.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct ExcludeTitle {
+    pub title: i32,
+    pub criterion: Option<i32>,
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct IncludeTitle {
+    pub title: i32,
+    pub criterion: Option<i32>,
+}
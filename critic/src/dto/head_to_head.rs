@@ -0,0 +1,36 @@
+use super::Rating;
+
+/// Everything recorded between two titles for a group as a whole, or for one
+/// of its criteria when `criterion` is set. Scores are from `a`'s point of
+/// view.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct HeadToHead {
+    pub group: i32,
+    pub criterion: Option<i32>,
+    pub rating_a: Rating,
+    pub rating_b: Rating,
+    /// Probability the rating model currently gives `a` being preferred.
+    pub expected: f32,
+    /// Score of each match between the two, oldest first.
+    pub outcomes: Vec<f32>,
+}
+
+impl HeadToHead {
+    pub fn wins(&self) -> usize {
+        self.outcomes.iter().filter(|x| **x > 0.5).count()
+    }
+
+    pub fn losses(&self) -> usize {
+        self.outcomes.iter().filter(|x| **x < 0.5).count()
+    }
+
+    pub fn ties(&self) -> usize {
+        self.outcomes.iter().filter(|x| **x == 0.5).count()
+    }
+
+    /// Average score, where a graded preference counts as a partial win.
+    pub fn score(&self) -> Option<f32> {
+        (!self.outcomes.is_empty())
+            .then(|| self.outcomes.iter().sum::<f32>() / self.outcomes.len() as f32)
+    }
+}
@@ -4,5 +4,128 @@ pub struct MatchResult {
     pub a: i32,
     pub b: i32,
     pub score: f32,
-    pub elo_change: (f32, f32),
+}
+
+/// Points on the scale the Rate tab judges with. Every scale is symmetric
+/// around a tie, stored as `score` between `0.0` and `1.0`.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum ScoreScale {
+    /// Win, tie or loss.
+    #[default]
+    Three,
+    Five,
+    Seven,
+}
+
+impl ScoreScale {
+    pub const ALL: [ScoreScale; 3] = [ScoreScale::Three, ScoreScale::Five, ScoreScale::Seven];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScoreScale::Three => "3",
+            ScoreScale::Five => "5",
+            ScoreScale::Seven => "7",
+        }
+    }
+
+    /// Steps between a tie and the strongest preference.
+    pub fn max_margin(&self) -> i32 {
+        match self {
+            ScoreScale::Three => 1,
+            ScoreScale::Five => 2,
+            ScoreScale::Seven => 3,
+        }
+    }
+
+    /// Score for preferring `a` by `margin` steps, or `b` when negative.
+    pub fn score(&self, margin: i32) -> f32 {
+        let max = self.max_margin();
+        0.5 + margin.clamp(-max, max) as f32 / (2 * max) as f32
+    }
+
+    pub fn label(&self, margin: i32) -> &'static str {
+        match (self.max_margin(), margin.abs()) {
+            (_, 0) => "Equal",
+            (2, 2) | (3, 3) => "Much better",
+            (3, 1) => "Slightly better",
+            _ => "Better",
+        }
+    }
+}
+
+impl std::fmt::Display for ScoreScale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ScoreScale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ScoreScale::ALL
+            .into_iter()
+            .find(|x| x.as_str() == s.trim())
+            .ok_or_else(|| format!("unknown score scale '{s}', expected 3, 5 or 7"))
+    }
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct SetScoreScale {
+    pub scale: ScoreScale,
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct MatchRecord {
+    pub id: i32,
+    pub criteria_group: i32,
+    pub criterion: i32,
+    pub a: i32,
+    pub b: i32,
+    pub score: f32,
+    pub elo_adj_a: f32,
+    pub elo_adj_b: f32,
+    pub criterion_adj_a: f32,
+    pub criterion_adj_b: f32,
+    pub time: String,
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct MatchAdjustment {
+    pub id: i32,
+    pub elo_adj_a: f32,
+    pub elo_adj_b: f32,
+    pub criterion_adj_a: f32,
+    pub criterion_adj_b: f32,
+}
+
+/// Deletes the most recent match and subtracts the rating adjustments it
+/// recorded. Other rating systems also track uncertainty, which the
+/// adjustments don't record, so under them ratings are recomputed from the
+/// remaining history instead. Saving reports `0` when there is nothing left
+/// to undo.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct UndoLastMatch;
+
+/// Deletes one match and recomputes ratings from the remaining history.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct DeleteMatch {
+    pub id: i32,
+}
+
+/// Reverses the outcome of one match and recomputes ratings.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct FlipMatch {
+    pub id: i32,
+}
+
+/// Replaces every match between `a` and `b` for `criterion` with a single new
+/// judgment, then recomputes ratings from the remaining history.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct Rejudge {
+    pub criteria_group: i32,
+    pub criterion: i32,
+    pub a: i32,
+    pub b: i32,
+    pub score: f32,
 }
@@ -1,13 +1,23 @@
-mod title;
 mod contest;
 mod criteria_group;
 mod criterion;
+mod exclusion;
+mod head_to_head;
 mod match_result;
+mod rating;
+mod skip;
+mod title;
 mod top_row;
+mod tournament;
 
-pub use title::*;
 pub use contest::*;
 pub use criteria_group::*;
 pub use criterion::*;
+pub use exclusion::*;
+pub use head_to_head::*;
 pub use match_result::*;
+pub use rating::*;
+pub use skip::*;
+pub use title::*;
 pub use top_row::*;
+pub use tournament::*;
@@ -0,0 +1,149 @@
+use super::MatchAdjustment;
+use crate::{
+    bradley_terry::BradleyTerry,
+    elo::{Elo, EloConfig},
+    glicko,
+    rating::RatingModel,
+};
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct Rating {
+    pub elo: f32,
+    pub deviation: f32,
+    pub volatility: f32,
+    /// Matches played, counted by storage rather than the rating model.
+    pub matches: i32,
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Self {
+            elo: 1000.0,
+            deviation: glicko::DEFAULT_DEVIATION,
+            volatility: glicko::DEFAULT_VOLATILITY,
+            matches: 0,
+        }
+    }
+}
+
+impl Rating {
+    /// The same rating with one more match counted.
+    pub fn played(self) -> Self {
+        Self {
+            matches: self.matches + 1,
+            ..self
+        }
+    }
+}
+
+/// A title's rating for a whole group, or for one of the group's criteria
+/// when `criterion` is set.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct TitleRating {
+    pub title: i32,
+    pub group: i32,
+    pub criterion: Option<i32>,
+    pub rating: Rating,
+}
+
+/// A title's rating right after one of its matches.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct RatingPoint {
+    pub match_id: i32,
+    pub time: String,
+    pub elo: f32,
+}
+
+/// How a title's rating for a group, or one of the group's criteria, moved
+/// over time.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct RatingHistory {
+    pub group: i32,
+    pub criterion: Option<i32>,
+    /// Rating before the first match.
+    pub start: f32,
+    /// Oldest first, ending at the current rating.
+    pub points: Vec<RatingPoint>,
+}
+
+impl RatingHistory {
+    /// The match after which the rating never again strayed more than
+    /// `within` points from where it is now.
+    pub fn stable_since(&self, within: f32) -> Option<&RatingPoint> {
+        let current = self.points.last()?.elo;
+        let settled = self
+            .points
+            .iter()
+            .rposition(|x| (x.elo - current).abs() > within)
+            .map_or(0, |x| x + 1);
+        self.points.get(settled)
+    }
+}
+
+/// Overwrites stored ratings and the adjustments recorded for each match.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct UpdateRatings {
+    pub ratings: Vec<TitleRating>,
+    pub adjustments: Vec<MatchAdjustment>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum RatingSystem {
+    #[default]
+    Elo,
+    Glicko2,
+    BradleyTerry,
+}
+
+impl RatingSystem {
+    pub const ALL: [RatingSystem; 3] = [
+        RatingSystem::Elo,
+        RatingSystem::Glicko2,
+        RatingSystem::BradleyTerry,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RatingSystem::Elo => "elo",
+            RatingSystem::Glicko2 => "glicko2",
+            RatingSystem::BradleyTerry => "bradley-terry",
+        }
+    }
+
+    pub fn model(&self, elo: &EloConfig) -> Box<dyn RatingModel> {
+        match self {
+            RatingSystem::Elo => Box::new(Elo {
+                config: elo.clone(),
+            }),
+            RatingSystem::Glicko2 => Box::new(glicko::Glicko2),
+            RatingSystem::BradleyTerry => Box::new(BradleyTerry),
+        }
+    }
+}
+
+impl std::fmt::Display for RatingSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for RatingSystem {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RatingSystem::ALL
+            .into_iter()
+            .find(|x| x.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown rating system '{s}'"))
+    }
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct SetRatingSystem {
+    pub system: RatingSystem,
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct SetEloConfig {
+    pub config: EloConfig,
+}
@@ -0,0 +1,25 @@
+pub const DEFAULT_SKIP_COOLDOWN: u32 = 24;
+
+/// Records that a pair could not be judged, keeping it out of
+/// `next_contest` until the skip cool-down has passed.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct SkipContest {
+    pub criterion: i32,
+    pub a: i32,
+    pub b: i32,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct Skip {
+    pub criterion: i32,
+    pub a: i32,
+    pub b: i32,
+    pub reason: Option<String>,
+    pub time: String,
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct SetSkipCooldown {
+    pub hours: u32,
+}
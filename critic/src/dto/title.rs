@@ -43,3 +43,64 @@ pub struct DeleteTitleCriteria {
     pub title: i32,
     pub criteria: i32,
 }
+
+/// Everything recorded about a title besides its ratings.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct TitleDetails {
+    pub id: i32,
+    pub name: String,
+    pub year: Option<i32>,
+    pub platform: Option<String>,
+    pub creator: Option<String>,
+    pub notes: Option<String>,
+    /// Free-form fields, such as external IDs, ordered by key.
+    pub fields: Vec<TitleField>,
+}
+
+impl TitleDetails {
+    /// Year, platform and creator, whichever are set, e.g.
+    /// "1997 · PlayStation · Square".
+    pub fn summary(&self) -> String {
+        let year = self.year.map(|x| x.to_string());
+        [
+            year.as_deref(),
+            self.platform.as_deref(),
+            self.creator.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ")
+    }
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct TitleField {
+    pub key: String,
+    pub value: String,
+}
+
+/// Replaces a title's typed details; `None` clears a detail.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct UpdateTitleDetails {
+    pub title: i32,
+    pub year: Option<i32>,
+    pub platform: Option<String>,
+    pub creator: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Sets a free-form field, e.g. `igdb` = `427`, replacing any earlier value
+/// for the key.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct SetTitleField {
+    pub title: i32,
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct DeleteTitleField {
+    pub title: i32,
+    pub key: String,
+}
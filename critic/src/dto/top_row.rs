@@ -3,4 +3,6 @@ pub struct TopRow {
     pub group: String,
     pub entry: String,
     pub elo: i32,
+    pub deviation: i32,
+    pub matches: i32,
 }
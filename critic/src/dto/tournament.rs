@@ -0,0 +1,60 @@
+/// A Swiss tournament over one criterion of a group, played over a fixed
+/// number of rounds.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct Tournament {
+    pub id: i32,
+    pub group: i32,
+    pub criterion: i32,
+    pub rounds: i32,
+    /// Last round that has been paired, `0` before the first.
+    pub round: i32,
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct TournamentPairing {
+    pub id: i32,
+    pub tournament: i32,
+    pub round: i32,
+    pub a: i32,
+    /// `None` when `a` sits the round out with a bye.
+    pub b: Option<i32>,
+    /// `a`'s score once the pair has been judged.
+    pub score: Option<f32>,
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct NewTournament {
+    pub criterion: i32,
+    pub rounds: i32,
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct DeleteTournament {
+    pub id: i32,
+}
+
+/// Pairs the next round of a tournament.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct NewRound {
+    pub tournament: i32,
+    pub round: i32,
+    pub pairings: Vec<(i32, Option<i32>)>,
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct ScorePairing {
+    pub id: i32,
+    pub score: f32,
+}
+
+/// Records the match for a tournament pairing and scores the pairing with
+/// it, so neither is saved without the other.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct ScoreTournamentMatch {
+    pub pairing: i32,
+    pub criteria_group: i32,
+    pub criterion: i32,
+    pub a: i32,
+    pub b: i32,
+    pub score: f32,
+}
@@ -1,29 +1,207 @@
-#[rustfmt::skip]
-fn k_factor(a: f32) -> f32 {
-    if a > 2400.0 { 5.0 }
-    else if a > 2200.0 { 10.0 }
-    else if a > 2000.0 { 15.0 }
-    else if a > 1800.0 { 20.0 }
-    else if a > 1600.0 { 25.0 }
-    else if a > 1400.0 { 30.0 }
-    else if a > 1200.0 { 35.0 }
-    else if a > 1000.0 { 40.0 }
-    else if a > 800.0 { 60.0 }
-    else { 80.0 }
-}
-
-pub fn calc_change(a: f32, b: f32, s: f32) -> (f32, f32) {
-    let k = k_factor(a);
-    let e = 1.0 / (1.0 + 10.0f32.powf((b - a) / 400.0));
+use crate::{dto::Rating, rating::RatingModel};
+
+/// Tunable parameters of the Elo model, stored in each database's settings.
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+pub struct EloConfig {
+    /// `(rating, k)` steps, highest rating first. A rating above a step's
+    /// threshold uses its K-factor.
+    pub k_schedule: Vec<(f32, f32)>,
+    /// K-factor for ratings at or below every step.
+    pub k_default: f32,
+    /// Matches a rating stays provisional for.
+    pub provisional_matches: i32,
+    /// K-factor while a rating is provisional, so newcomers settle quickly.
+    pub provisional_k: f32,
+    /// Ratings below the floor stop losing points.
+    pub floor: f32,
+    /// Rating every title starts from.
+    pub baseline: f32,
+}
+
+impl Default for EloConfig {
+    fn default() -> Self {
+        Self {
+            k_schedule: vec![
+                (2400.0, 5.0),
+                (2200.0, 10.0),
+                (2000.0, 15.0),
+                (1800.0, 20.0),
+                (1600.0, 25.0),
+                (1400.0, 30.0),
+                (1200.0, 35.0),
+                (1000.0, 40.0),
+                (800.0, 60.0),
+            ],
+            k_default: 80.0,
+            provisional_matches: 10,
+            provisional_k: 80.0,
+            floor: 100.0,
+            baseline: 1000.0,
+        }
+    }
+}
+
+impl EloConfig {
+    pub fn k_factor(&self, rating: &Rating) -> f32 {
+        if self.is_provisional(rating.matches) {
+            return self.provisional_k;
+        }
+
+        self.k_schedule
+            .iter()
+            .find(|(threshold, _)| rating.elo > *threshold)
+            .map(|(_, k)| *k)
+            .unwrap_or(self.k_default)
+    }
+
+    /// Whether a rating with `matches` played is still provisional.
+    pub fn is_provisional(&self, matches: i32) -> bool {
+        matches < self.provisional_matches
+    }
+
+    /// The rating a title has before its first match.
+    pub fn initial_rating(&self) -> Rating {
+        Rating {
+            elo: self.baseline,
+            ..Default::default()
+        }
+    }
+}
+
+/// Parses a K-factor schedule written as `rating:k` pairs separated by
+/// commas, e.g. `2000:16,1000:32`.
+pub fn parse_k_schedule(s: &str) -> Result<Vec<(f32, f32)>, String> {
+    let mut schedule = s
+        .split(',')
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .map(|step| {
+            let (rating, k) = step
+                .split_once(':')
+                .ok_or_else(|| format!("expected 'rating:k', found '{step}'"))?;
+            let parse = |x: &str| {
+                x.trim()
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|x| x.is_finite())
+                    .ok_or_else(|| format!("invalid number '{x}' in '{step}'"))
+            };
+            Ok((parse(rating)?, parse(k)?))
+        })
+        .collect::<Result<Vec<(f32, f32)>, String>>()?;
+    schedule.sort_by(|a, b| b.0.total_cmp(&a.0));
+    Ok(schedule)
+}
+
+/// Writes a K-factor schedule in the form [`parse_k_schedule`] reads.
+pub fn format_k_schedule(schedule: &[(f32, f32)]) -> String {
+    schedule
+        .iter()
+        .map(|(rating, k)| format!("{rating}:{k}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+pub fn expected(a: f32, b: f32) -> f32 {
+    1.0 / (1.0 + 10.0f32.powf((b - a) / 400.0))
+}
+
+pub fn calc_change(config: &EloConfig, a: &Rating, b: &Rating, s: f32) -> (f32, f32) {
+    let k = config.k_factor(a);
+    let e = expected(a.elo, b.elo);
     let c_a = k * (s - e);
 
-    let k = k_factor(b);
-    let e = 1.0 / (1.0 + 10.0f32.powf((a - b) / 400.0));
+    let k = config.k_factor(b);
+    let e = expected(b.elo, a.elo);
     let c_b = k * ((1.0 - s) - e);
 
     // Minimum values
-    let c_a = if a < 100.0 && c_a < 0.0 { 0.0 } else { c_a };
-    let c_b = if b < 100.0 && c_b < 0.0 { 0.0 } else { c_b };
+    let c_a = if a.elo < config.floor && c_a < 0.0 {
+        0.0
+    } else {
+        c_a
+    };
+    let c_b = if b.elo < config.floor && c_b < 0.0 {
+        0.0
+    } else {
+        c_b
+    };
 
     (c_a.round(), c_b.round())
 }
+
+#[derive(Debug, Default, Clone)]
+pub struct Elo {
+    pub config: EloConfig,
+}
+
+impl RatingModel for Elo {
+    fn expected(&self, a: &Rating, b: &Rating) -> f32 {
+        expected(a.elo, b.elo)
+    }
+
+    fn calc_change(&self, a: Rating, b: Rating, s: f32) -> (Rating, Rating) {
+        let (c_a, c_b) = calc_change(&self.config, &a, &b, s);
+        (
+            Rating {
+                elo: a.elo + c_a,
+                ..a
+            },
+            Rating {
+                elo: b.elo + c_b,
+                ..b
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedule_round_trips() {
+        let config = EloConfig::default();
+        let text = format_k_schedule(&config.k_schedule);
+        assert_eq!(parse_k_schedule(&text), Ok(config.k_schedule));
+
+        let schedule = parse_k_schedule("1000:32, 2000:16").unwrap();
+        assert_eq!(schedule, vec![(2000.0, 16.0), (1000.0, 32.0)]);
+        assert!(parse_k_schedule("2000").is_err());
+    }
+
+    #[test]
+    fn schedule_and_floor_drive_changes() {
+        let config = EloConfig {
+            k_schedule: vec![(1500.0, 10.0)],
+            k_default: 20.0,
+            provisional_matches: 3,
+            provisional_k: 40.0,
+            floor: 1000.0,
+            baseline: 1000.0,
+        };
+        let rating = |elo, matches| Rating {
+            elo,
+            matches,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            calc_change(&config, &rating(1600.0, 3), &rating(1600.0, 3), 1.0),
+            (5.0, -5.0)
+        );
+        assert_eq!(
+            calc_change(&config, &rating(1200.0, 3), &rating(1200.0, 3), 1.0),
+            (10.0, -10.0)
+        );
+        assert_eq!(
+            calc_change(&config, &rating(900.0, 3), &rating(900.0, 3), 0.0),
+            (0.0, 10.0)
+        );
+        // A newcomer moves further than the settled title it beat.
+        assert_eq!(
+            calc_change(&config, &rating(1600.0, 2), &rating(1600.0, 3), 1.0),
+            (20.0, -5.0)
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{dto::Rating, rating::RatingModel};
+
+/// Converts between the Glicko and Glicko-2 scales.
+const SCALE: f64 = 173.7178;
+const CENTER: f64 = 1500.0;
+
+/// Constrains how much the volatility can change between rating periods.
+const TAU: f64 = 0.5;
+const EPSILON: f64 = 0.000001;
+
+pub const DEFAULT_DEVIATION: f32 = 350.0;
+pub const DEFAULT_VOLATILITY: f32 = 0.06;
+/// Ratings less certain than this are still provisional, as on lichess.
+pub const PROVISIONAL_DEVIATION: f32 = 110.0;
+
+fn g(phi: f64) -> f64 {
+    1.0 / (1.0 + 3.0 * phi * phi / (std::f64::consts::PI * std::f64::consts::PI)).sqrt()
+}
+
+fn expected_score(mu: f64, mu_j: f64, phi_j: f64) -> f64 {
+    1.0 / (1.0 + (-g(phi_j) * (mu - mu_j)).exp())
+}
+
+fn volatility(sigma: f64, phi: f64, v: f64, delta: f64) -> f64 {
+    let a = (sigma * sigma).ln();
+    let f = |x: f64| {
+        let ex = x.exp();
+        let d = phi * phi + v + ex;
+        ex * (delta * delta - phi * phi - v - ex) / (2.0 * d * d) - (x - a) / (TAU * TAU)
+    };
+
+    let mut lo = a;
+    let mut hi = if delta * delta > phi * phi + v {
+        (delta * delta - phi * phi - v).ln()
+    } else {
+        let mut k = 1.0;
+        while f(a - k * TAU) < 0.0 {
+            k += 1.0;
+        }
+        a - k * TAU
+    };
+
+    let mut f_lo = f(lo);
+    let mut f_hi = f(hi);
+    while (hi - lo).abs() > EPSILON {
+        let c = lo + (lo - hi) * f_lo / (f_hi - f_lo);
+        let f_c = f(c);
+        if f_c * f_hi <= 0.0 {
+            lo = hi;
+            f_lo = f_hi;
+        } else {
+            f_lo /= 2.0;
+        }
+        hi = c;
+        f_hi = f_c;
+    }
+
+    (lo / 2.0).exp()
+}
+
+/// Rates `player` against every `(opponent, score)` played within a single
+/// rating period.
+pub fn rate(player: Rating, results: &[(Rating, f32)]) -> Rating {
+    let mu = (player.elo as f64 - CENTER) / SCALE;
+    let phi = player.deviation as f64 / SCALE;
+    let sigma = player.volatility as f64;
+
+    if results.is_empty() {
+        let phi = (phi * phi + sigma * sigma).sqrt();
+        return Rating {
+            deviation: ((phi * SCALE) as f32).min(DEFAULT_DEVIATION),
+            ..player
+        };
+    }
+
+    let mut v_inv = 0.0;
+    let mut improvement = 0.0;
+    for (opponent, s) in results {
+        let mu_j = (opponent.elo as f64 - CENTER) / SCALE;
+        let phi_j = opponent.deviation as f64 / SCALE;
+        let e = expected_score(mu, mu_j, phi_j);
+        let g_j = g(phi_j);
+        v_inv += g_j * g_j * e * (1.0 - e);
+        improvement += g_j * (*s as f64 - e);
+    }
+    let v = 1.0 / v_inv;
+    let delta = v * improvement;
+
+    let sigma = volatility(sigma, phi, v, delta);
+    let phi_star = (phi * phi + sigma * sigma).sqrt();
+    let phi = 1.0 / (1.0 / (phi_star * phi_star) + 1.0 / v).sqrt();
+    let mu = mu + phi * phi * improvement;
+
+    Rating {
+        elo: (mu * SCALE + CENTER) as f32,
+        deviation: (phi * SCALE) as f32,
+        volatility: sigma as f32,
+        ..player
+    }
+}
+
+/// Treats a single match as its own rating period for both contestants.
+pub fn calc_change(a: Rating, b: Rating, s: f32) -> (Rating, Rating) {
+    (rate(a, &[(b, s)]), rate(b, &[(a, 1.0 - s)]))
+}
+
+/// Probability that `a` beats `b`, accounting for the uncertainty of both.
+pub fn expected(a: &Rating, b: &Rating) -> f32 {
+    let mu = (a.elo as f64 - CENTER) / SCALE;
+    let mu_j = (b.elo as f64 - CENTER) / SCALE;
+    let phi = (a.deviation as f64 / SCALE).hypot(b.deviation as f64 / SCALE);
+    expected_score(mu, mu_j, phi) as f32
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Glicko2;
+
+impl RatingModel for Glicko2 {
+    fn expected(&self, a: &Rating, b: &Rating) -> f32 {
+        expected(a, b)
+    }
+
+    fn calc_change(&self, a: Rating, b: Rating, s: f32) -> (Rating, Rating) {
+        calc_change(a, b, s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glickman_example() {
+        // Worked example from Glickman's "Example of the Glicko-2 system".
+        let player = Rating {
+            elo: 1500.0,
+            deviation: 200.0,
+            volatility: 0.06,
+            ..Default::default()
+        };
+        let opponent = |elo, deviation| Rating {
+            elo,
+            deviation,
+            volatility: 0.06,
+            ..Default::default()
+        };
+
+        let result = rate(
+            player,
+            &[
+                (opponent(1400.0, 30.0), 1.0),
+                (opponent(1550.0, 100.0), 0.0),
+                (opponent(1700.0, 300.0), 0.0),
+            ],
+        );
+
+        assert!((result.elo - 1464.06).abs() < 0.05, "{}", result.elo);
+        assert!(
+            (result.deviation - 151.52).abs() < 0.05,
+            "{}",
+            result.deviation
+        );
+        assert!((result.volatility - 0.05999).abs() < 0.0001);
+    }
+
+    #[test]
+    fn winner_gains_and_uncertainty_shrinks() {
+        let (a, b) = calc_change(Rating::default(), Rating::default(), 1.0);
+        assert!(a.elo > 1000.0);
+        assert!(b.elo < 1000.0);
+        assert!(a.deviation < DEFAULT_DEVIATION);
+        assert!(b.deviation < DEFAULT_DEVIATION);
+    }
+}
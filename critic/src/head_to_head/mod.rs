@@ -0,0 +1,107 @@
+use crate::{
+    dto::{HeadToHead, MatchRecord, TitleRating},
+    CriticData, DbError,
+};
+
+/// Scores of every match between `a` and `b` for `group`, or only for
+/// `criterion` when set, from `a`'s point of view and oldest first.
+pub fn outcomes(
+    a: i32,
+    b: i32,
+    group: i32,
+    criterion: Option<i32>,
+    matches: &[MatchRecord],
+) -> Vec<f32> {
+    matches
+        .iter()
+        .filter(|m| m.criteria_group == group && criterion.is_none_or(|c| c == m.criterion))
+        .filter_map(|m| {
+            if m.a == a && m.b == b {
+                Some(m.score)
+            } else if m.a == b && m.b == a {
+                Some(1.0 - m.score)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Compares `a` and `b` in every group both belong to, first for the group
+/// as a whole and then for each of its criteria.
+pub fn compare<T: CriticData>(connection: &T, a: i32, b: i32) -> Result<Vec<HeadToHead>, DbError> {
+    let config = connection.elo_config()?;
+    let model = connection.rating_system()?.model(&config);
+    let initial = config.initial_rating();
+    let ratings = connection.ratings()?;
+    let matches = connection.matches()?;
+    let shared = connection.groups_by_title(b)?;
+
+    let rating = |title: i32, group: i32, criterion: Option<i32>| {
+        ratings
+            .iter()
+            .find(|x: &&TitleRating| {
+                x.title == title && x.group == group && x.criterion == criterion
+            })
+            .map_or(initial, |x| x.rating)
+    };
+
+    let mut results = Vec::new();
+    for group in connection.groups_by_title(a)? {
+        if !shared.iter().any(|x| x.id == group.id) {
+            continue;
+        }
+
+        let criteria = connection.criteria(group.id)?;
+        let keys = std::iter::once(None).chain(criteria.iter().map(|x| Some(x.id)));
+        for criterion in keys {
+            let rating_a = rating(a, group.id, criterion);
+            let rating_b = rating(b, group.id, criterion);
+            results.push(HeadToHead {
+                group: group.id,
+                criterion,
+                rating_a,
+                rating_b,
+                expected: model.expected(&rating_a, &rating_b),
+                outcomes: outcomes(a, b, group.id, criterion, &matches),
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "rusqlite")]
+    #[test]
+    fn compares_per_group_and_criterion() {
+        use crate::{critic_sqlite::test_connection, dto::MatchResult, CriticData, DbConnection};
+
+        let mut conn = test_connection();
+        for (a, b, score) in [(1, 2, 1.0), (2, 1, 0.25), (1, 2, 0.5)] {
+            conn.save(&MatchResult {
+                criteria_group: 1,
+                criterion: 1,
+                a,
+                b,
+                score,
+            })
+            .unwrap();
+        }
+
+        let rows = conn.head_to_head(1, 2).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].criterion, None);
+        assert_eq!(rows[1].criterion, Some(1));
+        for row in &rows {
+            assert_eq!(row.outcomes, vec![1.0, 0.75, 0.5]);
+            assert_eq!((row.wins(), row.losses(), row.ties()), (2, 0, 1));
+            assert!(row.expected > 0.5);
+        }
+
+        let reversed = conn.head_to_head(2, 1).unwrap();
+        assert_eq!(reversed[1].outcomes, vec![0.0, 0.25, 0.5]);
+        assert!((reversed[1].expected + rows[1].expected - 1.0).abs() < 1e-4);
+    }
+}
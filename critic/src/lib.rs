@@ -1,24 +1,60 @@
+pub mod bradley_terry;
+#[cfg(feature = "memory")]
+pub mod critic_memory;
 #[cfg(feature = "rusqlite")]
 pub mod critic_sqlite;
+pub mod cycles;
 pub mod dto;
 pub mod elo;
+pub mod glicko;
+pub mod head_to_head;
+pub mod placement;
+pub mod rating;
+pub mod replay;
+pub mod selection;
+pub mod swiss;
 
+/// Why a storage operation failed.
 #[derive(Debug)]
 pub enum DbError {
-    #[cfg(feature = "rusqlite")]
-    Sqlite(rusqlite::Error),
+    /// The record asked for doesn't exist.
+    NotFound,
+    /// A write would break a constraint, such as a name that is already
+    /// taken.
+    Constraint(String),
+    /// The database was written by a newer build, with schema version
+    /// `found` past the `supported` one.
+    SchemaTooNew { found: u32, supported: u32 },
+    /// Stored data can't be read, e.g. a damaged file or a value of the
+    /// wrong type.
+    Corrupt(String),
+    /// Any other failure of the storage backend.
+    Backend(Box<dyn std::error::Error + Send + Sync>),
 }
 
 impl std::fmt::Display for DbError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            #[cfg(feature = "rusqlite")]
-            DbError::Sqlite(err) => err.fmt(f),
+            DbError::NotFound => f.write_str("record not found"),
+            DbError::Constraint(msg) => write!(f, "constraint violated: {msg}"),
+            DbError::SchemaTooNew { found, supported } => write!(
+                f,
+                "database schema version {found} is newer than the {supported} this build supports"
+            ),
+            DbError::Corrupt(msg) => write!(f, "database is corrupt: {msg}"),
+            DbError::Backend(err) => err.fmt(f),
         }
     }
 }
 
-impl std::error::Error for DbError {}
+impl std::error::Error for DbError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DbError::Backend(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 pub trait Record<T> {
     fn save(&self, connection: &mut T) -> Result<usize, DbError>;
@@ -37,23 +73,77 @@ pub trait CriticData {
         count: usize,
         page: usize,
     ) -> Result<Vec<dto::TopRow>, DbError>;
+    fn top_criterion(
+        &self,
+        criterion: i32,
+        count: usize,
+        page: usize,
+    ) -> Result<Vec<dto::TopRow>, DbError>;
+    /// Ranks titles by the weighted average of their per-criterion ratings.
+    fn top_composite(
+        &self,
+        criteria_group: &str,
+        count: usize,
+        page: usize,
+    ) -> Result<Vec<dto::TopRow>, DbError>;
     fn all_groups(&self) -> Result<Vec<dto::CriteriaGroup>, DbError>;
     fn criteria(&self, id: i32) -> Result<Vec<dto::CriteriaGroupItem>, DbError>;
     fn all_titles(&self) -> Result<Vec<dto::Title>, DbError>;
+    /// A title with its metadata.
+    fn title_details(&self, title: i32) -> Result<dto::TitleDetails, DbError>;
     fn groups_by_title(&self, title_id: i32) -> Result<Vec<dto::CriteriaGroup>, DbError>;
     fn titles_in_group(&self, id: i32) -> Result<Vec<dto::Title>, DbError>;
+    fn rating_system(&self) -> Result<dto::RatingSystem, DbError>;
+    fn elo_config(&self) -> Result<elo::EloConfig, DbError>;
+    /// Hours a skipped pair is kept out of `next_contest`.
+    fn skip_cooldown(&self) -> Result<u32, DbError>;
+    fn contest_strategy(&self) -> Result<dto::ContestStrategy, DbError>;
+    fn score_scale(&self) -> Result<dto::ScoreScale, DbError>;
+    /// Skips still inside the cool-down, oldest first.
+    fn skips(&self) -> Result<Vec<dto::Skip>, DbError>;
+    /// Every recorded match, oldest first.
+    fn matches(&self) -> Result<Vec<dto::MatchRecord>, DbError>;
+    /// One page of matches, newest first, optionally only those involving
+    /// `title` or judged on `criterion`.
+    fn match_history(
+        &self,
+        title: Option<i32>,
+        criterion: Option<i32>,
+        count: usize,
+        page: usize,
+    ) -> Result<Vec<dto::MatchRecord>, DbError>;
+    fn ratings(&self) -> Result<Vec<dto::TitleRating>, DbError>;
+    /// How `title`'s ratings moved, one series per group and criterion.
+    fn rating_history(&self, title: i32) -> Result<Vec<dto::RatingHistory>, DbError>;
+    /// Every outcome between `a` and `b` in the groups they share, per group
+    /// and per criterion.
+    fn head_to_head(&self, a: i32, b: i32) -> Result<Vec<dto::HeadToHead>, DbError>;
+    /// Titles kept out of contests because the rater hasn't experienced them.
+    fn exclusions(&self) -> Result<Vec<dto::Exclusion>, DbError>;
+    /// The most recent tournament with rounds or pairings left to play.
+    fn active_tournament(&self) -> Result<Option<dto::Tournament>, DbError>;
+    fn tournament_pairings(&self, tournament: i32) -> Result<Vec<dto::TournamentPairing>, DbError>;
 }
 
 pub mod prelude {
+    #[cfg(feature = "memory")]
+    pub use crate::critic_memory::MemoryConnection;
     #[cfg(feature = "rusqlite")]
     pub use crate::critic_sqlite::Connection;
     pub use crate::{
         dto::{
-            CategoryItem, CriteriaGroup, CriteriaGroupItem, Criterion, DeleteCriteriaGroup,
-            DeleteCriterion, DeleteTitle, DeleteTitleCriteria, GroupAddToTiles, MatchResult,
-            NewCategoryItem, NewCriteriaGroup, NewCriterion, NewTitle, Title, UpdateCriteriaGroup,
-            UpdateCriterion, UpdateTitle,
+            CategoryItem, ContestStrategy, CriteriaGroup, CriteriaGroupItem, Criterion,
+            DeleteCriteriaGroup, DeleteCriterion, DeleteMatch, DeleteTitle, DeleteTitleCriteria,
+            DeleteTitleField, DeleteTournament, ExcludeTitle, FlipMatch, GroupAddToTiles,
+            IncludeTitle, MatchResult, NewCategoryItem, NewCriteriaGroup, NewCriterion, NewTitle,
+            NewTournament, Rating, RatingSystem, Rejudge, ScorePairing, ScoreScale,
+            ScoreTournamentMatch, SetContestStrategy, SetEloConfig, SetRatingSystem, SetScoreScale,
+            SetSkipCooldown, SetTitleField, SkipContest, Title, TitleDetails, UndoLastMatch,
+            UpdateCriteriaGroup, UpdateCriterion, UpdateCriterionWeight, UpdateRatings,
+            UpdateTitle, UpdateTitleDetails,
         },
+        elo::EloConfig,
+        rating::RatingModel,
         CriticData, DbConnection, DbError, Record,
     };
 }
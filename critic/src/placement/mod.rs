@@ -0,0 +1,186 @@
+use crate::{dto::MatchResult, CriticData, DbError};
+
+/// Binary insertion search placing one title into the ranking of a criterion
+/// with about `log2(N)` comparisons.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct Placement {
+    pub title: i32,
+    pub group: i32,
+    pub criterion: i32,
+    /// Titles already ranked for the criterion, best first.
+    pub ranked: Vec<i32>,
+    lo: usize,
+    hi: usize,
+}
+
+impl Placement {
+    pub fn new(title: i32, group: i32, criterion: i32, mut ranked: Vec<i32>) -> Self {
+        ranked.retain(|x| *x != title);
+        let hi = ranked.len();
+        Self {
+            title,
+            group,
+            criterion,
+            ranked,
+            lo: 0,
+            hi,
+        }
+    }
+
+    /// Places `title` against the same ordering `top_criterion` shows,
+    /// leaving out titles not experienced for the criterion.
+    pub fn start<T: CriticData>(
+        connection: &T,
+        title: i32,
+        group: i32,
+        criterion: i32,
+    ) -> Result<Self, DbError> {
+        let exclusions = connection.exclusions()?;
+        let ratings = connection.ratings()?;
+        let baseline = connection.elo_config()?.baseline;
+
+        let mut ranked: Vec<(i32, f32, String)> = connection
+            .titles_in_group(group)?
+            .into_iter()
+            .filter(|t| {
+                !exclusions
+                    .iter()
+                    .any(|x| x.title == t.id && x.criterion.is_none_or(|c| c == criterion))
+            })
+            .map(|t| {
+                let elo = ratings
+                    .iter()
+                    .find(|x| x.title == t.id && x.group == group && x.criterion == Some(criterion))
+                    .map(|x| x.rating.elo)
+                    .unwrap_or(baseline);
+                (t.id, elo, t.name)
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.2.cmp(&b.2)));
+
+        Ok(Self::new(
+            title,
+            group,
+            criterion,
+            ranked.into_iter().map(|x| x.0).collect(),
+        ))
+    }
+
+    /// The ranked title to compare against next, `None` once placed.
+    pub fn opponent(&self) -> Option<i32> {
+        (self.lo < self.hi).then(|| self.ranked[self.mid()])
+    }
+
+    /// Narrows the search with `title`'s score against [`Self::opponent`];
+    /// a tie places it right next to the opponent.
+    pub fn answer(&mut self, score: f32) {
+        if self.lo >= self.hi {
+            return;
+        }
+
+        let mid = self.mid();
+        if score > 0.5 {
+            self.hi = mid;
+        } else if score < 0.5 {
+            self.lo = mid + 1;
+        } else {
+            self.lo = mid;
+            self.hi = mid;
+        }
+    }
+
+    /// The match to record for `score` against the current opponent.
+    pub fn result(&self, score: f32) -> Option<MatchResult> {
+        self.opponent().map(|b| MatchResult {
+            criteria_group: self.group,
+            criterion: self.criterion,
+            a: self.title,
+            b,
+            score,
+        })
+    }
+
+    /// Zero-based rank the title was placed at, once the search is over.
+    pub fn position(&self) -> Option<usize> {
+        (self.lo >= self.hi).then_some(self.lo)
+    }
+
+    /// Comparisons left in the worst case.
+    pub fn remaining(&self) -> usize {
+        let span = self.hi - self.lo;
+        (usize::BITS - span.leading_zeros()) as usize
+    }
+
+    fn mid(&self) -> usize {
+        (self.lo + self.hi) / 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn places_with_logarithmic_comparisons() {
+        let ranked: Vec<i32> = (1..=100).collect();
+
+        for target in [0, 1, 37, 99, 100] {
+            let mut placement = Placement::new(0, 1, 1, ranked.clone());
+            assert_eq!(placement.remaining(), 7);
+
+            let mut comparisons = 0;
+            while let Some(opponent) = placement.opponent() {
+                // Title `n` sits at index `n - 1`; the new title beats every
+                // title from index `target` down.
+                let score = if opponent as usize > target { 1.0 } else { 0.0 };
+                placement.answer(score);
+                comparisons += 1;
+            }
+
+            assert_eq!(placement.position(), Some(target));
+            assert!(comparisons <= 7);
+            assert_eq!(placement.remaining(), 0);
+        }
+    }
+
+    #[cfg(feature = "rusqlite")]
+    #[test]
+    fn ranks_ties_like_top_criterion() {
+        use crate::critic_sqlite::test_connection;
+
+        let conn = test_connection();
+        conn.execute_batch(
+            "INSERT INTO titles (id, name) VALUES (3, 'AA'), (4, 'C');
+            INSERT INTO title_criteria (title_id, group_id) VALUES (3, 1), (4, 1);
+            INSERT INTO title_criterion (title_id, criterion_id, elo)
+                VALUES (1, 1, 1000.0), (2, 1, 1100.0), (3, 1, 1000.0), (4, 1, 1000.0);",
+        )
+        .unwrap();
+
+        let placement = Placement::start(&conn, 4, 1, 1).unwrap();
+        let names: Vec<String> = conn
+            .top_criterion(1, 10, 0)
+            .unwrap()
+            .into_iter()
+            .map(|x| x.entry)
+            .filter(|x| x != "C")
+            .collect();
+        let titles = conn.all_titles().unwrap();
+        let ranked: Vec<&str> = placement
+            .ranked
+            .iter()
+            .map(|id| titles.iter().find(|x| x.id == *id).unwrap().name.as_str())
+            .collect();
+        assert_eq!(ranked, names);
+    }
+
+    #[test]
+    fn tie_places_next_to_opponent() {
+        let mut placement = Placement::new(9, 1, 1, vec![1, 2, 3, 4, 5]);
+        assert_eq!(placement.opponent(), Some(3));
+
+        placement.answer(0.5);
+        assert_eq!(placement.opponent(), None);
+        assert_eq!(placement.position(), Some(2));
+    }
+}
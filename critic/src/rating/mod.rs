@@ -0,0 +1,39 @@
+use crate::dto::Rating;
+
+/// Turns the outcome of a single comparison into updated ratings.
+///
+/// `s` is the score from `a`'s point of view: `1.0` when `a` wins, `0.0` when
+/// `b` wins and `0.5` when they are equal. Graded scores in between count as
+/// a partial win, so a slight preference moves ratings less than a strong one.
+pub trait RatingModel {
+    /// Probability that `a` is preferred over `b`.
+    fn expected(&self, a: &Rating, b: &Rating) -> f32;
+
+    fn calc_change(&self, a: Rating, b: Rating, s: f32) -> (Rating, Rating);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dto::{RatingSystem, ScoreScale};
+
+    use super::*;
+
+    #[test]
+    fn stronger_preferences_move_ratings_further() {
+        let scale = ScoreScale::Seven;
+        for system in RatingSystem::ALL {
+            let model = system.model(&Default::default());
+            let moved = |margin: i32| {
+                let (a, _) =
+                    model.calc_change(Rating::default(), Rating::default(), scale.score(margin));
+                a.elo - Rating::default().elo
+            };
+
+            assert_eq!(moved(0), 0.0, "{system}");
+            for margin in 1..=scale.max_margin() {
+                assert!(moved(margin) > moved(margin - 1), "{system}");
+                assert_eq!(moved(-margin), -moved(margin), "{system}");
+            }
+        }
+    }
+}
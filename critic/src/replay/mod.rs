@@ -0,0 +1,279 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    dto::{
+        MatchAdjustment, MatchRecord, Rating, RatingHistory, RatingPoint, TitleRating,
+        UpdateRatings,
+    },
+    rating::RatingModel,
+    CriticData, DbConnection, DbError, Record,
+};
+
+type Key = (i32, i32, Option<i32>);
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct RatingMove {
+    pub title: i32,
+    pub group: i32,
+    pub criterion: Option<i32>,
+    pub before: f32,
+    pub after: f32,
+}
+
+impl RatingMove {
+    pub fn change(&self) -> f32 {
+        self.after - self.before
+    }
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct RecomputeReport {
+    pub matches: usize,
+    /// Ratings that changed, largest change first.
+    pub moves: Vec<RatingMove>,
+}
+
+impl RecomputeReport {
+    /// Number of distinct titles with at least one changed rating.
+    pub fn titles_moved(&self) -> usize {
+        let mut titles: Vec<i32> = self.moves.iter().map(|x| x.title).collect();
+        titles.sort_unstable();
+        titles.dedup();
+        titles.len()
+    }
+}
+
+/// Resets every rating in `ratings` to `initial` and replays `matches` in
+/// order through `model`.
+pub fn replay(
+    model: &dyn RatingModel,
+    initial: Rating,
+    ratings: &[TitleRating],
+    matches: &[MatchRecord],
+) -> UpdateRatings {
+    let mut state: HashMap<Key, Rating> = ratings
+        .iter()
+        .map(|x| ((x.title, x.group, x.criterion), initial))
+        .collect();
+
+    let mut adjustments = Vec::with_capacity(matches.len());
+    for m in matches {
+        let group = (
+            state.get(&(m.a, m.criteria_group, None)).copied(),
+            state.get(&(m.b, m.criteria_group, None)).copied(),
+        );
+        let (a, b) = model.calc_change(
+            group.0.unwrap_or(initial),
+            group.1.unwrap_or(initial),
+            m.score,
+        );
+        let mut adjustment = MatchAdjustment {
+            id: m.id,
+            elo_adj_a: a.elo - group.0.unwrap_or(initial).elo,
+            elo_adj_b: b.elo - group.1.unwrap_or(initial).elo,
+            ..Default::default()
+        };
+        // Titles no longer in the group keep no group rating.
+        if group.0.is_some() {
+            state.insert((m.a, m.criteria_group, None), a.played());
+        }
+        if group.1.is_some() {
+            state.insert((m.b, m.criteria_group, None), b.played());
+        }
+
+        let key_a = (m.a, m.criteria_group, Some(m.criterion));
+        let key_b = (m.b, m.criteria_group, Some(m.criterion));
+        let criterion = (
+            state.get(&key_a).copied().unwrap_or(initial),
+            state.get(&key_b).copied().unwrap_or(initial),
+        );
+        let (a, b) = model.calc_change(criterion.0, criterion.1, m.score);
+        adjustment.criterion_adj_a = a.elo - criterion.0.elo;
+        adjustment.criterion_adj_b = b.elo - criterion.1.elo;
+        adjustments.push(adjustment);
+        state.insert(key_a, a.played());
+        state.insert(key_b, b.played());
+    }
+
+    let mut ratings: Vec<TitleRating> = state
+        .into_iter()
+        .map(|((title, group, criterion), rating)| TitleRating {
+            title,
+            group,
+            criterion,
+            rating,
+        })
+        .collect();
+    ratings.sort_by_key(|x| (x.title, x.group, x.criterion));
+
+    UpdateRatings {
+        ratings,
+        adjustments,
+    }
+}
+
+/// Rating trajectories of `title` for each group and criterion it has
+/// played in, worked back from its current `ratings` through the adjustments
+/// recorded in `matches`. Ratings the title no longer holds are left out.
+pub fn history(title: i32, ratings: &[TitleRating], matches: &[MatchRecord]) -> Vec<RatingHistory> {
+    // Points hold the adjustment each match made until the ratings are known.
+    let mut series: BTreeMap<(i32, Option<i32>), Vec<RatingPoint>> = BTreeMap::new();
+    for m in matches {
+        let (adj, criterion_adj) = if m.a == title {
+            (m.elo_adj_a, m.criterion_adj_a)
+        } else if m.b == title {
+            (m.elo_adj_b, m.criterion_adj_b)
+        } else {
+            continue;
+        };
+
+        for (key, adj) in [
+            ((m.criteria_group, None), adj),
+            ((m.criteria_group, Some(m.criterion)), criterion_adj),
+        ] {
+            series.entry(key).or_default().push(RatingPoint {
+                match_id: m.id,
+                time: m.time.clone(),
+                elo: adj,
+            });
+        }
+    }
+
+    series
+        .into_iter()
+        .filter_map(|((group, criterion), points)| {
+            let current = ratings
+                .iter()
+                .find(|x| x.title == title && x.group == group && x.criterion == criterion)?;
+
+            let mut elo = current.rating.elo;
+            let mut points: Vec<RatingPoint> = points
+                .into_iter()
+                .rev()
+                .map(|point| {
+                    let adj = point.elo;
+                    let point = RatingPoint { elo, ..point };
+                    elo -= adj;
+                    point
+                })
+                .collect();
+            points.reverse();
+
+            Some(RatingHistory {
+                group,
+                criterion,
+                start: elo,
+                points,
+            })
+        })
+        .collect()
+}
+
+/// Recomputes every rating from `match_history` using the active rating
+/// model and reports which ratings moved.
+pub fn recompute<T>(connection: &mut T) -> Result<RecomputeReport, DbError>
+where
+    T: CriticData + DbConnection,
+    UpdateRatings: Record<T>,
+{
+    let (update, report) = rebuild(connection)?;
+    connection.save(&update)?;
+
+    Ok(report)
+}
+
+/// The ratings [`recompute`] would save, and the report it would give,
+/// without saving them.
+pub fn rebuild<T: CriticData>(connection: &T) -> Result<(UpdateRatings, RecomputeReport), DbError> {
+    let config = connection.elo_config()?;
+    let model = connection.rating_system()?.model(&config);
+    let before = connection.ratings()?;
+    let matches = connection.matches()?;
+
+    let update = replay(&*model, config.initial_rating(), &before, &matches);
+
+    let before: HashMap<Key, f32> = before
+        .into_iter()
+        .map(|x| ((x.title, x.group, x.criterion), x.rating.elo))
+        .collect();
+
+    let mut moves: Vec<RatingMove> = update
+        .ratings
+        .iter()
+        .map(|x| RatingMove {
+            title: x.title,
+            group: x.group,
+            criterion: x.criterion,
+            before: before
+                .get(&(x.title, x.group, x.criterion))
+                .copied()
+                .unwrap_or(config.baseline),
+            after: x.rating.elo,
+        })
+        .filter(|x| x.change().abs() >= 0.5)
+        .collect();
+    moves.sort_by(|a, b| b.change().abs().total_cmp(&a.change().abs()));
+
+    let report = RecomputeReport {
+        matches: matches.len(),
+        moves,
+    };
+    Ok((update, report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elo::Elo;
+
+    fn record(id: i32, a: i32, b: i32, score: f32) -> MatchRecord {
+        MatchRecord {
+            id,
+            criteria_group: 1,
+            criterion: 1,
+            a,
+            b,
+            score,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn replay_matches_incremental_updates() {
+        let ratings: Vec<TitleRating> = (1..=3)
+            .map(|title| TitleRating {
+                title,
+                group: 1,
+                criterion: None,
+                rating: Rating {
+                    elo: 1234.0,
+                    ..Default::default()
+                },
+            })
+            .collect();
+        let matches = [record(1, 1, 2, 1.0), record(2, 2, 3, 0.5)];
+
+        let elo = Elo::default();
+        let update = replay(&elo, Rating::default(), &ratings, &matches);
+
+        let (a, b) = elo.calc_change(Rating::default(), Rating::default(), 1.0);
+        let (b, c) = elo.calc_change(b, Rating::default(), 0.5);
+        let group: Vec<f32> = update
+            .ratings
+            .iter()
+            .filter(|x| x.criterion.is_none())
+            .map(|x| x.rating.elo)
+            .collect();
+        assert_eq!(group, vec![a.elo, b.elo, c.elo]);
+
+        assert_eq!(update.adjustments[0].elo_adj_a, a.elo - 1000.0);
+        assert_eq!(
+            update
+                .ratings
+                .iter()
+                .filter(|x| x.criterion.is_some())
+                .count(),
+            3
+        );
+    }
+}
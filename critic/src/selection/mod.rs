@@ -0,0 +1,289 @@
+use std::{
+    collections::{HashMap, HashSet},
+    f32::consts::{LN_10, LN_2, PI},
+};
+
+use crate::{
+    dto::{Contest, ContestStrategy, Contestant, Criterion, Rating, RatingSystem},
+    glicko, CriticData, DbError,
+};
+
+/// Logits per rating point on the Elo scale.
+const LOGIT: f32 = LN_10 / 400.0;
+
+/// A title's rating for one criterion and how uncertain it is.
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
+pub struct Belief {
+    pub elo: f32,
+    /// Standard deviation of the rating, in rating points.
+    pub deviation: f32,
+}
+
+/// Mutual information, in bits, between the outcome of `a` against `b` and
+/// their ratings.
+///
+/// Close pairs with uncertain ratings score highest; a pair whose outcome is
+/// a foregone conclusion, or whose ratings are already well known, scores
+/// close to zero.
+pub fn information_gain(a: &Belief, b: &Belief) -> f32 {
+    // Probit approximation of the logistic curve (Houlsby et al., 2011).
+    let k = (PI / 8.0).sqrt();
+    let mean = (a.elo - b.elo) * LOGIT * k;
+    let variance = (a.deviation.powi(2) + b.deviation.powi(2)) * (LOGIT * k).powi(2);
+    let c2 = PI * LN_2 / 2.0;
+
+    let predicted = entropy(phi(mean / (1.0 + variance).sqrt()));
+    let expected =
+        c2.sqrt() / (variance + c2).sqrt() * (-mean * mean / (2.0 * (variance + c2))).exp();
+    (predicted - expected).max(0.0)
+}
+
+/// Picks the next contest using the database's contest strategy, or
+/// [`DbError::NotFound`] when no pair is left to offer.
+pub fn next_contest<T: CriticData>(connection: &T) -> Result<Contest, DbError> {
+    match connection.contest_strategy()? {
+        ContestStrategy::Closest => connection.next_contest(),
+        ContestStrategy::InformationGain => most_informative(connection)?.ok_or(DbError::NotFound),
+    }
+}
+
+/// The contest with the highest [`information_gain`] among pairs that have
+/// never been compared, or among every pair once none are left. Excluded
+/// titles and skipped pairs are never offered.
+pub fn most_informative<T: CriticData>(connection: &T) -> Result<Option<Contest>, DbError> {
+    let system = connection.rating_system()?;
+    let initial = connection.elo_config()?.initial_rating();
+    let exclusions = connection.exclusions()?;
+    let skipped: HashSet<(i32, i32, i32)> = connection
+        .skips()?
+        .into_iter()
+        .map(|x| pair(x.criterion, x.a, x.b))
+        .collect();
+
+    let played: HashSet<(i32, i32, i32)> = connection
+        .matches()?
+        .into_iter()
+        .map(|m| pair(m.criterion, m.a, m.b))
+        .collect();
+
+    let mut ratings: HashMap<(i32, i32, Option<i32>), Rating> = HashMap::new();
+    for x in connection.ratings()? {
+        ratings.insert((x.title, x.group, x.criterion), x.rating);
+    }
+    let rating = |title: i32, group: i32, criterion: Option<i32>| {
+        ratings
+            .get(&(title, group, criterion))
+            .copied()
+            .unwrap_or(initial)
+    };
+    let belief = |title: i32, group: i32, criterion: i32| {
+        let rating = rating(title, group, Some(criterion));
+        let deviation = match system {
+            RatingSystem::Glicko2 => rating.deviation,
+            // Other systems don't track uncertainty, so it shrinks with the
+            // number of matches played instead.
+            _ => glicko::DEFAULT_DEVIATION / (1.0 + rating.matches as f32).sqrt(),
+        };
+        Belief {
+            elo: rating.elo,
+            deviation,
+        }
+    };
+
+    // (unplayed, gain) so a never-compared pair always beats a rematch.
+    let mut best: Option<((bool, f32), Contest)> = None;
+    for group in connection.all_groups()? {
+        let titles = connection.titles_in_group(group.id)?;
+        for criterion in connection.criteria(group.id)? {
+            let available: Vec<_> = titles
+                .iter()
+                .filter(|t| {
+                    !exclusions
+                        .iter()
+                        .any(|x| x.title == t.id && x.criterion.is_none_or(|c| c == criterion.id))
+                })
+                .collect();
+
+            for (i, a) in available.iter().enumerate() {
+                for b in &available[i + 1..] {
+                    let key = pair(criterion.id, a.id, b.id);
+                    if skipped.contains(&key) {
+                        continue;
+                    }
+
+                    let score = (
+                        !played.contains(&key),
+                        information_gain(
+                            &belief(a.id, group.id, criterion.id),
+                            &belief(b.id, group.id, criterion.id),
+                        ),
+                    );
+                    if best.as_ref().is_some_and(|(best, _)| *best >= score) {
+                        continue;
+                    }
+
+                    let contestant = |title: &crate::dto::Title| Contestant {
+                        id: title.id,
+                        name: title.name.clone(),
+                        elo: rating(title.id, group.id, None).elo,
+                        criterion_elo: rating(title.id, group.id, Some(criterion.id)).elo,
+                    };
+                    best = Some((
+                        score,
+                        Contest {
+                            criterion: Criterion {
+                                group: group.id,
+                                id: criterion.id,
+                                name: criterion.name.clone(),
+                                group_name: group.name.clone(),
+                            },
+                            a: contestant(a),
+                            b: contestant(b),
+                        },
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(best.map(|(_, contest)| contest))
+}
+
+/// Builds the contest between titles `a` and `b` for `criterion`, or `None`
+/// if the criterion or either title no longer exists.
+pub fn contest_for<T: CriticData>(
+    connection: &T,
+    criterion: i32,
+    a: i32,
+    b: i32,
+) -> Result<Option<Contest>, DbError> {
+    let mut found = None;
+    for group in connection.all_groups()? {
+        if let Some(item) = connection
+            .criteria(group.id)?
+            .into_iter()
+            .find(|x| x.id == criterion)
+        {
+            found = Some(Criterion {
+                group: group.id,
+                id: item.id,
+                name: item.name,
+                group_name: group.name,
+            });
+            break;
+        }
+    }
+    let Some(criterion) = found else {
+        return Ok(None);
+    };
+
+    let titles = connection.all_titles()?;
+    let ratings = connection.ratings()?;
+    let baseline = connection.elo_config()?.baseline;
+    let contestant = |id: i32| {
+        let title = titles.iter().find(|x| x.id == id)?;
+        let rating = |key: Option<i32>| {
+            ratings
+                .iter()
+                .find(|x| x.title == id && x.group == criterion.group && x.criterion == key)
+                .map(|x| x.rating.elo)
+                .unwrap_or(baseline)
+        };
+        Some(Contestant {
+            id,
+            name: title.name.clone(),
+            elo: rating(None),
+            criterion_elo: rating(Some(criterion.id)),
+        })
+    };
+
+    Ok(match (contestant(a), contestant(b)) {
+        (Some(a), Some(b)) => Some(Contest { criterion, a, b }),
+        _ => None,
+    })
+}
+
+fn pair(criterion: i32, a: i32, b: i32) -> (i32, i32, i32) {
+    (criterion, a.min(b), a.max(b))
+}
+
+fn entropy(p: f32) -> f32 {
+    [p, 1.0 - p]
+        .iter()
+        .filter(|p| **p > 0.0)
+        .map(|p| -p * p.log2())
+        .sum()
+}
+
+/// Standard normal cumulative distribution function.
+fn phi(x: f32) -> f32 {
+    0.5 * (1.0 + erf(x / std::f32::consts::SQRT_2))
+}
+
+/// Abramowitz and Stegun approximation 7.1.26.
+fn erf(x: f32) -> f32 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+    let y = 1.0
+        - (((((1.061_405_4 * t - 1.453_152_1) * t) + 1.421_413_8) * t - 0.284_496_74) * t
+            + 0.254_829_6)
+            * t
+            * (-x * x).exp();
+    y.copysign(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefers_close_and_uncertain_pairs() {
+        let belief = |elo, deviation| Belief { elo, deviation };
+
+        let close = information_gain(&belief(1000.0, 350.0), &belief(1000.0, 350.0));
+        let distant = information_gain(&belief(1000.0, 350.0), &belief(1600.0, 350.0));
+        let known = information_gain(&belief(1000.0, 30.0), &belief(1000.0, 30.0));
+
+        assert!(close > distant);
+        assert!(close > known);
+        assert!(known > 0.0);
+    }
+
+    #[cfg(feature = "rusqlite")]
+    #[test]
+    fn rematches_once_pairs_are_exhausted() {
+        use crate::{
+            critic_sqlite::test_connection,
+            dto::{ExcludeTitle, MatchResult, SetContestStrategy},
+            DbConnection,
+        };
+
+        let mut conn = test_connection();
+        conn.save(&SetContestStrategy {
+            strategy: ContestStrategy::InformationGain,
+        })
+        .unwrap();
+        let first = next_contest(&conn).unwrap();
+        assert_eq!((first.a.id, first.b.id), (1, 2));
+
+        conn.save(&MatchResult {
+            criteria_group: 1,
+            criterion: 1,
+            a: 1,
+            b: 2,
+            score: 1.0,
+        })
+        .unwrap();
+        assert!(conn.next_contest().is_err());
+
+        let rematch = next_contest(&conn).unwrap();
+        assert_eq!((rematch.a.id, rematch.b.id), (1, 2));
+        assert!(rematch.a.criterion_elo > rematch.b.criterion_elo);
+
+        conn.save(&ExcludeTitle {
+            title: 1,
+            criterion: None,
+        })
+        .unwrap();
+        assert!(matches!(next_contest(&conn), Err(DbError::NotFound)));
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::HashSet;
+
+use crate::{
+    dto::{NewRound, Tournament, TournamentPairing},
+    CriticData, DbConnection, DbError, Record,
+};
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct Standing {
+    pub title: i32,
+    /// One point per win, half per tie and one per bye.
+    pub score: f32,
+    /// Criterion rating, used to break ties in `score`.
+    pub elo: f32,
+    pub byes: usize,
+}
+
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
+pub struct RoundProgress {
+    pub round: i32,
+    pub rounds: i32,
+    /// Pairs judged so far in the current round.
+    pub judged: usize,
+    pub pairs: usize,
+}
+
+/// Enough rounds to separate the top of the field with a few to spare,
+/// without more rounds than there are opponents.
+pub fn recommended_rounds(titles: usize) -> i32 {
+    let rounds = (titles.max(1) as f32).log2().ceil() as i32 + 2;
+    rounds.min(titles.saturating_sub(1) as i32)
+}
+
+/// Scores each of `entrants` (title, criterion rating) from `pairings`,
+/// leader first.
+pub fn standings(entrants: &[(i32, f32)], pairings: &[TournamentPairing]) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = entrants
+        .iter()
+        .map(|(title, elo)| Standing {
+            title: *title,
+            elo: *elo,
+            ..Default::default()
+        })
+        .collect();
+
+    let mut add = |title: i32, score: f32, bye: bool| {
+        if let Some(x) = standings.iter_mut().find(|x| x.title == title) {
+            x.score += score;
+            x.byes += bye as usize;
+        }
+    };
+    for p in pairings {
+        match (p.b, p.score) {
+            (None, _) => add(p.a, 1.0, true),
+            (Some(b), Some(score)) => {
+                add(p.a, score, false);
+                add(b, 1.0 - score, false);
+            }
+            (Some(_), None) => {}
+        }
+    }
+
+    standings.sort_by(|a, b| b.score.total_cmp(&a.score).then(b.elo.total_cmp(&a.elo)));
+    standings
+}
+
+/// Pairs titles with neighbours of similar score, avoiding rematches where
+/// possible. With an odd field the lowest placed title with the fewest byes
+/// sits out.
+pub fn pair_round(
+    standings: &[Standing],
+    pairings: &[TournamentPairing],
+) -> Vec<(i32, Option<i32>)> {
+    let played: HashSet<(i32, i32)> = pairings
+        .iter()
+        .filter_map(|p| p.b.map(|b| (p.a.min(b), p.a.max(b))))
+        .collect();
+
+    let mut pool: Vec<&Standing> = standings.iter().collect();
+    let bye = if pool.len() % 2 == 1 {
+        let fewest = pool.iter().map(|x| x.byes).min().unwrap_or(0);
+        let idx = pool.iter().rposition(|x| x.byes == fewest).unwrap_or(0);
+        Some(pool.remove(idx).title)
+    } else {
+        None
+    };
+
+    let mut round = Vec::with_capacity(pool.len() / 2 + 1);
+    while !pool.is_empty() {
+        let a = pool.remove(0).title;
+        let idx = pool
+            .iter()
+            .position(|x| !played.contains(&(a.min(x.title), a.max(x.title))))
+            .unwrap_or(0);
+        if idx < pool.len() {
+            round.push((a, Some(pool.remove(idx).title)));
+        }
+    }
+    round.extend(bye.map(|x| (x, None)));
+    round
+}
+
+/// Titles taking part in `tournament` with their criterion rating.
+pub fn entrants<T: CriticData>(
+    connection: &T,
+    tournament: &Tournament,
+) -> Result<Vec<(i32, f32)>, DbError> {
+    let exclusions = connection.exclusions()?;
+    let ratings = connection.ratings()?;
+    let baseline = connection.elo_config()?.baseline;
+
+    Ok(connection
+        .titles_in_group(tournament.group)?
+        .into_iter()
+        .filter(|t| {
+            !exclusions
+                .iter()
+                .any(|x| x.title == t.id && x.criterion.is_none_or(|c| c == tournament.criterion))
+        })
+        .map(|t| {
+            let elo = ratings
+                .iter()
+                .find(|x| {
+                    x.title == t.id
+                        && x.group == tournament.group
+                        && x.criterion == Some(tournament.criterion)
+                })
+                .map(|x| x.rating.elo)
+                .unwrap_or(baseline);
+            (t.id, elo)
+        })
+        .collect())
+}
+
+/// The next pair to judge in `tournament`, pairing a new round once the
+/// current one is complete. Returns `None` when every round has been played.
+pub fn next_pairing<T>(
+    connection: &mut T,
+    tournament: &Tournament,
+) -> Result<Option<TournamentPairing>, DbError>
+where
+    T: CriticData + DbConnection,
+    NewRound: Record<T>,
+{
+    let mut round = tournament.round;
+    loop {
+        let pairings = connection.tournament_pairings(tournament.id)?;
+        if let Some(pending) = pairings
+            .iter()
+            .find(|p| p.round == round && p.b.is_some() && p.score.is_none())
+        {
+            return Ok(Some(pending.clone()));
+        }
+        if round >= tournament.rounds {
+            return Ok(None);
+        }
+
+        let entrants = entrants(connection, tournament)?;
+        if entrants.len() < 2 {
+            return Ok(None);
+        }
+
+        round += 1;
+        let pairings = pair_round(&standings(&entrants, &pairings), &pairings);
+        connection.save(&NewRound {
+            tournament: tournament.id,
+            round,
+            pairings,
+        })?;
+    }
+}
+
+/// How far the current round of `tournament` has been played.
+pub fn progress(tournament: &Tournament, pairings: &[TournamentPairing]) -> RoundProgress {
+    let current: Vec<_> = pairings
+        .iter()
+        .filter(|p| p.round == tournament.round && p.b.is_some())
+        .collect();
+
+    RoundProgress {
+        round: tournament.round,
+        rounds: tournament.rounds,
+        judged: current.iter().filter(|p| p.score.is_some()).count(),
+        pairs: current.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairing(round: i32, a: i32, b: Option<i32>, score: Option<f32>) -> TournamentPairing {
+        TournamentPairing {
+            round,
+            a,
+            b,
+            score,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn pairs_by_score_without_rematches() {
+        let entrants = [
+            (1, 1000.0),
+            (2, 1000.0),
+            (3, 1000.0),
+            (4, 1000.0),
+            (5, 1000.0),
+        ];
+        let played = [
+            pairing(1, 1, Some(2), Some(1.0)),
+            pairing(1, 3, Some(4), Some(1.0)),
+            pairing(1, 5, None, None),
+        ];
+
+        let standings = standings(&entrants, &played);
+        let leaders: Vec<i32> = standings.iter().take(3).map(|x| x.title).collect();
+        assert_eq!(leaders, vec![1, 3, 5]);
+
+        let round = pair_round(&standings, &played);
+        assert_eq!(round.len(), 3);
+        assert!(round.contains(&(1, Some(3))));
+        assert!(round.iter().all(|x| x.1.is_some() || x.0 != 5));
+        assert!(!round.contains(&(1, Some(2))));
+    }
+
+    #[test]
+    fn recommended_rounds_grow_slowly() {
+        assert_eq!(recommended_rounds(2), 1);
+        assert_eq!(recommended_rounds(60), 8);
+        assert_eq!(recommended_rounds(100), 9);
+    }
+
+    #[cfg(feature = "rusqlite")]
+    #[test]
+    fn plays_every_round() {
+        use crate::{
+            critic_sqlite::test_connection,
+            dto::{MatchResult, NewTournament, ScorePairing},
+        };
+
+        let mut conn = test_connection();
+        conn.execute_batch(
+            "INSERT INTO titles (id, name) VALUES (3, 'C'), (4, 'D'), (5, 'E');
+            INSERT INTO title_criteria (title_id, group_id) VALUES (3, 1), (4, 1), (5, 1);",
+        )
+        .unwrap();
+        conn.save(&NewTournament {
+            criterion: 1,
+            rounds: 3,
+        })
+        .unwrap();
+
+        while let Some(tournament) = conn.active_tournament().unwrap() {
+            let pairing = next_pairing(&mut conn, &tournament).unwrap().unwrap();
+            let b = pairing.b.unwrap();
+            let score = if pairing.a < b { 1.0 } else { 0.0 };
+            conn.save(&MatchResult {
+                criteria_group: 1,
+                criterion: 1,
+                a: pairing.a,
+                b,
+                score,
+            })
+            .unwrap();
+            conn.save(&ScorePairing {
+                id: pairing.id,
+                score,
+            })
+            .unwrap();
+        }
+
+        let pairings = conn.tournament_pairings(1).unwrap();
+        assert_eq!(pairings.iter().filter(|x| x.b.is_some()).count(), 6);
+        let byes: HashSet<i32> = pairings
+            .iter()
+            .filter(|x| x.b.is_none())
+            .map(|x| x.a)
+            .collect();
+        assert_eq!(byes.len(), 3);
+        assert_eq!(conn.matches().unwrap().len(), 6);
+    }
+}